@@ -0,0 +1,100 @@
+// abap-tokenizer/src/abapgit/filename.rs
+//! Interpretation of abapGit file names.
+//!
+//! abapGit serializes every repository object into files named
+//! `<name>.<type>[.<part>].<extension>`, such as `zcl_foo.clas.abap`,
+//! `zcl_foo.clas.locals_imp.abap` or `zfoo.fugr.lzfootop.abap`. Namespaced
+//! names use `#` instead of `/`, as in `#abc#cl_foo.clas.abap`.
+use std::path::Path;
+
+/// Extension of ABAP source files.
+pub const ABAP_EXTENSION: &str = "abap";
+/// Extension of the object metadata files.
+pub const XML_EXTENSION: &str = "xml";
+/// Name of the package description files, which do not name their package.
+const PACKAGE_FILE_NAME: &str = "package.devc.xml";
+/// Object type of function groups, whose parts are include programs.
+pub const FUNCTION_GROUP: &str = "FUGR";
+
+/// A file of an abapGit repository, identified by its name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbapGitFile {
+    /// The object type, in upper case, such as `CLAS` or `PROG`.
+    pub object_type: String,
+    /// The object name, in upper case, with the namespace restored, such as
+    /// `/ABC/CL_FOO`.
+    pub object_name: String,
+    /// The part of the object, such as `locals_imp` or a function group
+    /// include, or None for the main file of the object.
+    pub part: Option<String>,
+    /// The file extension, such as `abap` or `xml`.
+    pub extension: String,
+}
+
+impl AbapGitFile {
+    /// Interprets the name of a file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file
+    ///
+    /// # Returns
+    ///
+    /// The interpreted file name, or None if the name does not follow the
+    /// abapGit convention (for example `.abapgit.xml`) or does not name its
+    /// object (`package.devc.xml`).
+    pub fn parse(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        if file_name.starts_with('.') || file_name.eq_ignore_ascii_case(PACKAGE_FILE_NAME) {
+            return None;
+        }
+        let fields: Vec<&str> = file_name.split('.').collect();
+        if fields.len() < 3 || fields.iter().any(|field| field.is_empty()) {
+            return None;
+        }
+        let part = fields[2..fields.len() - 1].join(".");
+        Some(AbapGitFile {
+            object_type: fields[1].to_uppercase(),
+            object_name: unescape_name(fields[0]),
+            part: (!part.is_empty()).then_some(part),
+            extension: fields[fields.len() - 1].to_lowercase(),
+        })
+    }
+
+    /// Returns whether the file contains ABAP source code.
+    pub fn is_abap(&self) -> bool {
+        self.extension == ABAP_EXTENSION
+    }
+
+    /// Returns whether the file contains the metadata of its object.
+    pub fn is_metadata(&self) -> bool {
+        self.extension == XML_EXTENSION
+    }
+
+    /// Returns the name of the program the file contains, as used in
+    /// `INCLUDE` statements.
+    ///
+    /// # Returns
+    ///
+    /// The upper-case name of the include program for the parts of a function
+    /// group, and the object name for any other file.
+    pub fn program_name(&self) -> String {
+        match &self.part {
+            Some(part) if self.object_type == FUNCTION_GROUP => unescape_name(part),
+            _ => self.object_name.clone(),
+        }
+    }
+}
+
+/// Restores an object name from its file name form.
+///
+/// # Arguments
+///
+/// * `name` - The name as it appears in a file name, such as `#abc#cl_foo`
+///
+/// # Returns
+///
+/// The upper-case object name, such as `/ABC/CL_FOO`.
+pub fn unescape_name(name: &str) -> String {
+    name.replace('#', "/").to_uppercase()
+}
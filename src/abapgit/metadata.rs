@@ -0,0 +1,499 @@
+// abap-tokenizer/src/abapgit/metadata.rs
+//! Reading of the XML metadata files of abapGit objects.
+//!
+//! abapGit stores the attributes of an object next to its source, in
+//! `<name>.<type>.xml`. The values are serialized as asXML:
+//!
+//! ```xml
+//! <abapGit version="v1.0.0" serializer="LCL_OBJECT_PROG" serializer_version="v1.0.0">
+//!  <asx:abap xmlns:asx="http://www.sap.com/abapxml" version="1.0">
+//!   <asx:values>
+//!    <PROGDIR><NAME>ZFOO</NAME><SUBC>1</SUBC></PROGDIR>
+//!    <TPOOL><item><ID>I</ID><KEY>001</KEY><ENTRY>Hello</ENTRY></item></TPOOL>
+//!   </asx:values>
+//!  </asx:abap>
+//! </abapGit>
+//! ```
+//!
+//! Only the parts relevant for analysing the code are read: the program or
+//! class header, the description, the text pool and the flow logic of the
+//! screens (`DYNPROS`). The format is simple enough to be read with a small
+//! element scanner, which covers what abapGit and the asXML serializer emit:
+//! elements without mixed content, entity and character references, CDATA
+//! sections, comments and the XML declaration.
+use crate::encoding::Encoding;
+use regex::Regex;
+use std::collections::BTreeMap;
+
+/// Regex matching the encoding declared in the XML declaration.
+const DECLARED_ENCODING: &str = r#"^<\?xml\s[^>]*\bencoding\s*=\s*["']([^"']*)["']"#;
+/// Regex matching the serializer attribute of the root element.
+const SERIALIZER: &str = r#"<abapGit\s[^>]*\bserializer="([^"]*)""#;
+/// Element holding the serialized values.
+const VALUES: &str = "asx:values";
+/// Element holding the text pool.
+const TEXT_POOL: &str = "TPOOL";
+/// Delimiters of a CDATA section, whose content is not parsed.
+const CDATA_START: &str = "<![CDATA[";
+const CDATA_END: &str = "]]>";
+/// Delimiters of a comment.
+const COMMENT_START: &str = "<!--";
+const COMMENT_END: &str = "-->";
+/// Element of every entry of a table.
+const ITEM: &str = "item";
+/// Records holding the header attributes of programs, classes and interfaces.
+const HEADER_RECORDS: &[&str] = &["PROGDIR", "VSEOCLASS", "VSEOINTERF"];
+/// Elements holding the description of an object, by preference.
+const DESCRIPTION_ELEMENTS: &[&str] = &["DESCRIPT", "AREAT", "CTEXT", "DDTEXT"];
+/// Header attribute holding the program type.
+const PROGRAM_TYPE: &str = "SUBC";
+/// Element holding the screens of a program.
+const DYNPROS: &str = "DYNPROS";
+/// Element of a screen holding its attributes.
+const DYNPRO_HEADER: &str = "HEADER";
+/// Screen attribute holding the screen number.
+const SCREEN_NUMBER: &str = "SCREEN";
+/// Screen attribute holding the description of the screen.
+const SCREEN_DESCRIPTION: &str = "DESCRIPT";
+/// Element of a screen holding its flow logic, one `LINE` per item.
+const FLOW_LOGIC: &str = "FLOW_LOGIC";
+/// Field of a flow logic item holding the line.
+const FLOW_LOGIC_LINE: &str = "LINE";
+
+/// The type of a program, as in its `SUBC` attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgramType {
+    /// `1`: an executable program (report).
+    Executable,
+    /// `I`: an include program.
+    Include,
+    /// `M`: a module pool.
+    ModulePool,
+    /// `F`: a function group.
+    FunctionGroup,
+    /// `S`: a subroutine pool.
+    SubroutinePool,
+    /// `K`: a class pool.
+    ClassPool,
+    /// `J`: an interface pool.
+    InterfacePool,
+    /// `T`: a type pool.
+    TypePool,
+}
+
+impl ProgramType {
+    /// Returns the program type for a `SUBC` code.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The value of the `SUBC` attribute
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "1" => Some(ProgramType::Executable),
+            "I" => Some(ProgramType::Include),
+            "M" => Some(ProgramType::ModulePool),
+            "F" => Some(ProgramType::FunctionGroup),
+            "S" => Some(ProgramType::SubroutinePool),
+            "K" => Some(ProgramType::ClassPool),
+            "J" => Some(ProgramType::InterfacePool),
+            "T" => Some(ProgramType::TypePool),
+            _ => None,
+        }
+    }
+}
+
+/// The kind of a text pool entry, as in its `ID` field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextPoolId {
+    /// `R`: the program title.
+    Title,
+    /// `I`: a text symbol, used as `TEXT-xxx`.
+    TextSymbol,
+    /// `S`: the selection text of a parameter or select option.
+    SelectionText,
+    /// `H`: a list heading.
+    ListHeading,
+    /// Any other kind, with its code.
+    Other(String),
+}
+
+impl TextPoolId {
+    /// Returns the kind for an `ID` code.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The value of the `ID` field
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "R" => TextPoolId::Title,
+            "I" => TextPoolId::TextSymbol,
+            "S" => TextPoolId::SelectionText,
+            "H" => TextPoolId::ListHeading,
+            _ => TextPoolId::Other(code.to_string()),
+        }
+    }
+}
+
+/// An entry of the text pool of a program or class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextPoolEntry {
+    /// The kind of the entry.
+    pub id: TextPoolId,
+    /// The key of the entry, such as `001` for a text symbol. Empty for the title.
+    pub key: String,
+    /// The text.
+    pub entry: String,
+    /// The maximum length of the text, if given.
+    pub length: Option<usize>,
+}
+
+/// A screen of a program, with its flow logic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dynpro {
+    /// The screen number, such as `0100`.
+    pub number: String,
+    /// The description of the screen.
+    pub description: Option<String>,
+    /// The flow logic, one line per source line.
+    pub flow_logic: String,
+}
+
+/// The metadata of an abapGit object.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ObjectMetadata {
+    /// The abapGit serializer that wrote the file, such as `LCL_OBJECT_PROG`.
+    pub serializer: Option<String>,
+    /// The short description of the object.
+    pub description: Option<String>,
+    /// The header attributes of a program, class or interface, such as the
+    /// `SUBC` and `FIXPT` program attributes.
+    pub attributes: BTreeMap<String, String>,
+    /// The text pool entries, in file order.
+    pub text_pool: Vec<TextPoolEntry>,
+    /// The screens of a program, in file order.
+    pub dynpros: Vec<Dynpro>,
+}
+
+impl ObjectMetadata {
+    /// Reads the metadata from the content of an abapGit XML file.
+    ///
+    /// # Arguments
+    ///
+    /// * `xml` - The content of the file
+    ///
+    /// # Returns
+    ///
+    /// The metadata. Missing elements leave the corresponding fields empty.
+    pub fn parse(xml: &str) -> Self {
+        let mut metadata = ObjectMetadata {
+            serializer: Regex::new(SERIALIZER)
+                .ok()
+                .and_then(|re| re.captures(xml).map(|captures| unescape(&captures[1]))),
+            ..Default::default()
+        };
+        let Some(values) = find_element(xml, VALUES) else {
+            return metadata;
+        };
+        if let Some(header) = HEADER_RECORDS
+            .iter()
+            .find_map(|record| find_element(values, record))
+        {
+            metadata.attributes = children(header)
+                .into_iter()
+                .map(|(name, content)| (name.to_string(), text(content)))
+                .collect();
+        }
+        if let Some(text_pool) = find_element(values, TEXT_POOL) {
+            metadata.text_pool = children(text_pool)
+                .into_iter()
+                .filter(|(name, _)| *name == ITEM)
+                .map(|(_, item)| text_pool_entry(item))
+                .collect();
+        }
+        if let Some(dynpros) = find_element(values, DYNPROS) {
+            metadata.dynpros = children(dynpros)
+                .into_iter()
+                .filter(|(name, _)| *name == ITEM)
+                .map(|(_, item)| dynpro(item))
+                .collect();
+        }
+        // Screens have descriptions of their own, which do not describe the object
+        let records: Vec<&str> = children(values)
+            .into_iter()
+            .filter(|(name, _)| *name != DYNPROS)
+            .map(|(_, content)| content)
+            .collect();
+        metadata.description = DESCRIPTION_ELEMENTS
+            .iter()
+            .find_map(|element| {
+                records
+                    .iter()
+                    .find_map(|record| find_element(record, element))
+            })
+            .map(text)
+            .or_else(|| {
+                metadata
+                    .text_pool
+                    .iter()
+                    .find(|entry| entry.id == TextPoolId::Title)
+                    .map(|entry| entry.entry.clone())
+            });
+        metadata
+    }
+
+    /// Returns a header attribute.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The attribute name, such as `SUBC`
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(String::as_str)
+    }
+
+    /// Returns the program type given by the `SUBC` attribute.
+    pub fn program_type(&self) -> Option<ProgramType> {
+        self.attribute(PROGRAM_TYPE).and_then(ProgramType::from_code)
+    }
+
+    /// Returns the text pool entry of a text symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The three-character key of the text symbol
+    pub fn text_symbol(&self, key: &str) -> Option<&TextPoolEntry> {
+        self.text_pool.iter().find(|entry| {
+            entry.id == TextPoolId::TextSymbol && entry.key.eq_ignore_ascii_case(key)
+        })
+    }
+}
+
+/// Returns the encoding named in the XML declaration of a file.
+///
+/// Only the start of the file is looked at, so the bytes need not be
+/// decoded. A UTF-16 file has a byte order mark, which takes precedence.
+///
+/// # Arguments
+///
+/// * `bytes` - The content of the file
+///
+/// # Returns
+///
+/// The declared encoding, or None if there is no declaration or the
+/// encoding is not supported.
+pub fn declared_encoding(bytes: &[u8]) -> Option<Encoding> {
+    let prolog = String::from_utf8_lossy(&bytes[..bytes.len().min(200)]);
+    let declared = Regex::new(DECLARED_ENCODING).ok()?.captures(&prolog)?[1].to_string();
+    declared.parse().ok()
+}
+
+/// Builds a text pool entry from the content of an `item` element.
+fn text_pool_entry(item: &str) -> TextPoolEntry {
+    let field = |name| find_element(item, name).map(text).unwrap_or_default();
+    TextPoolEntry {
+        id: TextPoolId::from_code(&field("ID")),
+        key: field("KEY"),
+        entry: field("ENTRY"),
+        length: field("LENGTH").trim().parse().ok(),
+    }
+}
+
+/// Builds a screen from the content of an `item` element of `DYNPROS`.
+fn dynpro(item: &str) -> Dynpro {
+    let header = find_element(item, DYNPRO_HEADER).unwrap_or_default();
+    let flow_logic = find_element(item, FLOW_LOGIC).map_or_else(Vec::new, |lines| {
+        children(lines)
+            .into_iter()
+            .filter(|(name, _)| *name == ITEM)
+            .map(|(_, line)| find_element(line, FLOW_LOGIC_LINE).map(text).unwrap_or_default())
+            .collect()
+    });
+    Dynpro {
+        number: find_element(header, SCREEN_NUMBER).map(text).unwrap_or_default(),
+        description: find_element(header, SCREEN_DESCRIPTION).map(text),
+        flow_logic: flow_logic.join("\n"),
+    }
+}
+
+/// Returns the content of the first element named `name`, at any depth.
+fn find_element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let mut offset = 0;
+    while let Some((tag_name, content, end)) = next_element(xml, offset) {
+        if tag_name == name {
+            return Some(content);
+        }
+        // Descend into the element before continuing with its siblings
+        if let Some(found) = find_element(content, name) {
+            return Some(found);
+        }
+        offset = end;
+    }
+    None
+}
+
+/// Returns the direct child elements of an element content, as name and content.
+fn children(xml: &str) -> Vec<(&str, &str)> {
+    let mut elements = Vec::new();
+    let mut offset = 0;
+    while let Some((name, content, end)) = next_element(xml, offset) {
+        elements.push((name, content));
+        offset = end;
+    }
+    elements
+}
+
+/// Finds the next element starting at or after `offset`.
+///
+/// # Returns
+///
+/// The element name, its content and the offset just past its closing tag,
+/// or None if no further element exists.
+fn next_element(xml: &str, offset: usize) -> Option<(&str, &str, usize)> {
+    let mut position = offset;
+    loop {
+        let start = position + xml[position..].find('<')?;
+        if let Some(end) = markup_end(xml, start) {
+            position = end;
+            continue;
+        }
+        let tag_end = start + 1 + xml[start + 1..].find('>')?;
+        position = tag_end + 1;
+        // Skip stray closing tags
+        let tag = &xml[start + 1..tag_end];
+        if tag.starts_with('/') {
+            continue;
+        }
+        let name = tag_name(tag);
+        if tag.ends_with('/') {
+            return Some((name, "", position));
+        }
+        let (content_end, end) = closing_tag(xml, name, position)?;
+        return Some((name, &xml[position..content_end], end));
+    }
+}
+
+/// Finds the closing tag of an element named `name` whose content starts at
+/// `start`, skipping nested elements of the same name.
+///
+/// # Returns
+///
+/// The offset of the closing tag and the offset just past it.
+fn closing_tag(xml: &str, name: &str, start: usize) -> Option<(usize, usize)> {
+    let mut depth = 0;
+    let mut position = start;
+    loop {
+        let tag_start = position + xml[position..].find('<')?;
+        if let Some(end) = markup_end(xml, tag_start) {
+            position = end;
+            continue;
+        }
+        let tag_end = tag_start + 1 + xml[tag_start + 1..].find('>')?;
+        position = tag_end + 1;
+        let tag = &xml[tag_start + 1..tag_end];
+        if let Some(closing) = tag.strip_prefix('/') {
+            if closing.trim_end() == name {
+                if depth == 0 {
+                    return Some((tag_start, position));
+                }
+                depth -= 1;
+            }
+        } else if tag_name(tag) == name && !tag.ends_with('/') {
+            depth += 1;
+        }
+    }
+}
+
+/// Returns the name of an element from the text of its start tag.
+fn tag_name(tag: &str) -> &str {
+    tag.split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default()
+}
+
+/// Returns the offset just past a declaration, comment or CDATA section
+/// starting at `start`, or None if an element tag starts there.
+///
+/// Comments and CDATA sections may contain `<` and `>`, so they are skipped
+/// up to their own terminator. An unterminated one extends to the end.
+fn markup_end(xml: &str, start: usize) -> Option<usize> {
+    let rest = &xml[start..];
+    let terminator = if rest.starts_with(CDATA_START) {
+        CDATA_END
+    } else if rest.starts_with(COMMENT_START) {
+        COMMENT_END
+    } else if rest.starts_with("<?") {
+        "?>"
+    } else if rest.starts_with("<!") {
+        ">"
+    } else {
+        return None;
+    };
+    Some(
+        rest[2..]
+            .find(terminator)
+            .map_or(xml.len(), |index| start + 2 + index + terminator.len()),
+    )
+}
+
+/// Returns the text of an element content: CDATA sections are taken
+/// literally, comments are dropped and the references in the rest are
+/// replaced.
+fn text(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find("<!") {
+        result.push_str(&unescape(&rest[..start]));
+        rest = &rest[start..];
+        if let Some(section) = rest.strip_prefix(CDATA_START) {
+            let end = section.find(CDATA_END).unwrap_or(section.len());
+            result.push_str(&section[..end]);
+            rest = section.get(end + CDATA_END.len()..).unwrap_or_default();
+        } else if let Some(comment) = rest.strip_prefix(COMMENT_START) {
+            rest = comment
+                .find(COMMENT_END)
+                .map_or("", |end| &comment[end + COMMENT_END.len()..]);
+        } else {
+            result.push_str("<!");
+            rest = &rest[2..];
+        }
+    }
+    result.push_str(&unescape(rest));
+    result
+}
+
+/// Replaces the XML entity and character references in a text.
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let Some(end) = rest.find(';') else {
+            break;
+        };
+        let reference = &rest[1..end];
+        let character = match reference {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => reference
+                .strip_prefix("#x")
+                .map(|hex| u32::from_str_radix(hex, 16).ok())
+                .unwrap_or_else(|| reference.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match character {
+            Some(character) => {
+                result.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
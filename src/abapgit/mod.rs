@@ -0,0 +1,371 @@
+// abap-tokenizer/src/abapgit/mod.rs
+//! Reading of abapGit repositories.
+//!
+//! abapGit serializes each repository object, such as a class or a program,
+//! into one or more source files and an XML metadata file (see `filename`).
+//! This module scans a checkout, groups the files by object, reads the
+//! metadata and tokenizes the source parts, so that reports can be keyed by
+//! the object type and name instead of the file path.
+//!
+//! Besides ABAP, objects can have sources in other languages, tokenized with
+//! the built-in profile their file extension selects (`Dialect::for_file`):
+//! CDS definitions (`.ddls.asddls`), behavior definitions (`.bdef.asbdef`)
+//! and the flow logic of screens, which abapGit embeds in the program XML
+//! (`DYNPROS`) or in `.prog.screen_<number>.xml` files.
+
+/// Interpretation of abapGit file names.
+pub mod filename;
+
+/// Reading of the XML metadata files.
+pub mod metadata;
+
+pub use filename::AbapGitFile;
+pub use metadata::{Dynpro, ObjectMetadata, ProgramType, TextPoolEntry, TextPoolId};
+
+use crate::config::{Dialect, TokenizerConfig};
+use crate::encoding::{read_source, Encoding, SourceText};
+use crate::error::TokenizerError;
+use crate::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use crate::tokenizer::token::Token;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A file belonging to an abapGit object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectFile {
+    /// The part of the object, such as `locals_imp`, or None for the main file.
+    pub part: Option<String>,
+    /// The file extension, such as `abap` or `xml`.
+    pub extension: String,
+    /// The path of the file.
+    pub path: PathBuf,
+}
+
+/// Prefix of the part name of a screen, followed by the screen number.
+const SCREEN_PART_PREFIX: &str = "screen_";
+
+impl ObjectFile {
+    /// Returns whether the file contains ABAP source code.
+    pub fn is_abap(&self) -> bool {
+        self.extension == filename::ABAP_EXTENSION
+    }
+
+    /// Returns the built-in profile for the language of the file, or None for
+    /// ABAP sources and metadata files.
+    pub fn dialect(&self) -> Option<Dialect> {
+        Dialect::for_file(&self.path)
+    }
+
+    /// Returns whether the file contains source code, in ABAP or in another
+    /// language with a built-in profile.
+    pub fn is_source(&self) -> bool {
+        self.is_abap() || self.dialect().is_some()
+    }
+}
+
+/// An object of an abapGit repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbapObject {
+    /// The object type, such as `CLAS`, `INTF`, `PROG` or `FUGR`.
+    pub object_type: String,
+    /// The object name, such as `ZCL_FOO` or `/ABC/CL_FOO`.
+    pub name: String,
+    /// The files of the object, main file first, then the parts by name.
+    pub files: Vec<ObjectFile>,
+    /// The metadata read from the main XML file, if the object has one.
+    pub metadata: Option<ObjectMetadata>,
+}
+
+impl AbapObject {
+    /// Returns the source files of the object, in ABAP or in another language.
+    pub fn source_files(&self) -> impl Iterator<Item = &ObjectFile> {
+        self.files.iter().filter(|file| file.is_source())
+    }
+
+    /// Tokenizes the source files of the object and the flow logic of its
+    /// screens.
+    ///
+    /// ABAP sources are tokenized with `config`; sources in other languages
+    /// with the built-in profile of their language, reading them in the
+    /// encoding of `config`. Every screen becomes a part of its own, named
+    /// `screen_<number>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The TokenizerConfig to use for the ABAP sources
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<PartTokens>, TokenizerError>` - The tokens of each source
+    ///   file and screen, or an error if a file cannot be read or tokenized.
+    pub fn tokenize(&self, config: &TokenizerConfig) -> Result<Vec<PartTokens>, TokenizerError> {
+        let mut parts = Vec::new();
+        for file in self.source_files() {
+            let dialect = file.dialect();
+            if dialect == Some(Dialect::Dynpro) {
+                let dynpros = read_dynpros(&file.path, config.metadata.encoding)?;
+                parts.extend(tokenize_dynpros(&dynpros, &file.path, config)?);
+                continue;
+            }
+            let config = match dialect {
+                Some(dialect) => dialect_config(dialect, config)?,
+                None => config.clone(),
+            };
+            let source = read_source(&file.path, config.metadata.encoding)?;
+            let tokens = FlexibleTokenizer::new(&source.text, config).tokenize()?;
+            parts.push(PartTokens {
+                part: file.part.clone(),
+                path: file.path.clone(),
+                dialect,
+                source,
+                tokens,
+            });
+        }
+        if let Some(metadata) = &self.metadata {
+            let main_file = self
+                .files
+                .iter()
+                .find(|file| file.part.is_none() && file.extension == filename::XML_EXTENSION);
+            if let Some(main_file) = main_file {
+                parts.extend(tokenize_dynpros(&metadata.dynpros, &main_file.path, config)?);
+            }
+        }
+        Ok(parts)
+    }
+}
+
+impl fmt::Display for AbapObject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.object_type, self.name)
+    }
+}
+
+/// The tokens of one source file of an object.
+#[derive(Debug, Clone)]
+pub struct PartTokens {
+    /// The part of the object, or None for the main file.
+    pub part: Option<String>,
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The built-in profile the part was tokenized with, or None for ABAP.
+    pub dialect: Option<Dialect>,
+    /// The decoded source of the file. For screens, the flow logic read
+    /// from the XML file, which token spans refer to.
+    pub source: SourceText,
+    /// The tokens of the file.
+    pub tokens: Vec<Token>,
+}
+
+/// The result of an analysis for every source file of an object.
+#[derive(Debug, Clone)]
+pub struct ObjectReport<T> {
+    /// The object type.
+    pub object_type: String,
+    /// The object name.
+    pub name: String,
+    /// The result for each source file, with its part.
+    pub parts: Vec<(Option<String>, T)>,
+}
+
+/// The objects of an abapGit checkout.
+#[derive(Debug, Clone, Default)]
+pub struct AbapGitRepository {
+    /// The objects, ordered by type and name.
+    pub objects: Vec<AbapObject>,
+}
+
+impl AbapGitRepository {
+    /// Scans a directory of an abapGit checkout, usually its `src` folder.
+    ///
+    /// Files whose names do not follow the abapGit convention, such as
+    /// `.abapgit.xml`, are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The directory to scan, including its subdirectories
+    /// * `encoding` - The encoding of the metadata files, if known (see
+    ///   `read_metadata`)
+    ///
+    /// # Returns
+    ///
+    /// * `Result<AbapGitRepository, TokenizerError>` - The objects found, or an
+    ///   error if a directory or metadata file cannot be read or decoded.
+    pub fn scan(root: &Path, encoding: Option<Encoding>) -> Result<Self, TokenizerError> {
+        let mut files = Vec::new();
+        collect_files(root, &mut files)?;
+
+        let mut objects: BTreeMap<(String, String), AbapObject> = BTreeMap::new();
+        for (file, path) in files {
+            let object = objects
+                .entry((file.object_type.clone(), file.object_name.clone()))
+                .or_insert_with(|| AbapObject {
+                    object_type: file.object_type.clone(),
+                    name: file.object_name.clone(),
+                    files: Vec::new(),
+                    metadata: None,
+                });
+            if file.is_metadata() && file.part.is_none() {
+                object.metadata = Some(read_metadata(&path, encoding)?);
+            }
+            object.files.push(ObjectFile {
+                part: file.part,
+                extension: file.extension,
+                path,
+            });
+        }
+        for object in objects.values_mut() {
+            object
+                .files
+                .sort_by(|a, b| (&a.part, &a.extension).cmp(&(&b.part, &b.extension)));
+        }
+        Ok(AbapGitRepository {
+            objects: objects.into_values().collect(),
+        })
+    }
+
+    /// Returns an object by type and name.
+    ///
+    /// # Arguments
+    ///
+    /// * `object_type` - The object type, such as `CLAS`
+    /// * `name` - The object name, such as `ZCL_FOO`
+    pub fn object(&self, object_type: &str, name: &str) -> Option<&AbapObject> {
+        self.objects.iter().find(|object| {
+            object.object_type.eq_ignore_ascii_case(object_type)
+                && object.name.eq_ignore_ascii_case(name)
+        })
+    }
+
+    /// Runs an analysis on the tokens of every source file, grouped by object.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The TokenizerConfig to use for tokenization
+    /// * `analyse` - The analysis, such as `analysis::text_symbols::find_text_symbols`
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<ObjectReport<T>>, TokenizerError>` - One report for every
+    ///   object with source files, or an error if a file cannot be read or
+    ///   tokenized.
+    pub fn reports<T>(
+        &self,
+        config: &TokenizerConfig,
+        analyse: impl Fn(&[Token]) -> T,
+    ) -> Result<Vec<ObjectReport<T>>, TokenizerError> {
+        let mut reports = Vec::new();
+        for object in &self.objects {
+            let parts = object.tokenize(config)?;
+            if parts.is_empty() {
+                continue;
+            }
+            reports.push(ObjectReport {
+                object_type: object.object_type.clone(),
+                name: object.name.clone(),
+                parts: parts
+                    .into_iter()
+                    .map(|part| (part.part, analyse(&part.tokens)))
+                    .collect(),
+            });
+        }
+        Ok(reports)
+    }
+}
+
+/// Reads an abapGit XML metadata file.
+///
+/// The file is decoded like the sources (see `encoding`): a byte order mark
+/// takes precedence, then the given encoding, then the encoding named in the
+/// XML declaration, and UTF-8 otherwise.
+///
+/// # Arguments
+///
+/// * `path` - The path of the XML file
+/// * `encoding` - The encoding of the file, if known
+///
+/// # Returns
+///
+/// * `Result<ObjectMetadata, TokenizerError>` - The metadata, or an error if
+///   the file cannot be read or decoded.
+pub fn read_metadata(path: &Path, encoding: Option<Encoding>) -> Result<ObjectMetadata, TokenizerError> {
+    let bytes =
+        fs::read(path).map_err(|e| TokenizerError::IoError(format!("{}: {}", path.display(), e)))?;
+    let encoding = encoding.or_else(|| metadata::declared_encoding(&bytes));
+    let xml = SourceText::decode(&bytes, encoding).map_err(|e| match e {
+        TokenizerError::EncodingError(message) => {
+            TokenizerError::EncodingError(format!("{}: {}", path.display(), message))
+        }
+        other => other,
+    })?;
+    Ok(ObjectMetadata::parse(&xml.text))
+}
+
+/// Reads the screens of an abapGit XML file, such as `zfoo.prog.xml` or
+/// `zfoo.prog.screen_0100.xml`.
+///
+/// # Arguments
+///
+/// * `path` - The path of the XML file
+/// * `encoding` - The encoding of the file, if known
+///
+/// # Returns
+///
+/// * `Result<Vec<Dynpro>, TokenizerError>` - The screens with their flow
+///   logic, or an error if the file cannot be read.
+pub fn read_dynpros(path: &Path, encoding: Option<Encoding>) -> Result<Vec<Dynpro>, TokenizerError> {
+    Ok(read_metadata(path, encoding)?.dynpros)
+}
+
+/// Tokenizes the flow logic of screens with the Dynpro profile.
+fn tokenize_dynpros(
+    dynpros: &[Dynpro],
+    path: &Path,
+    config: &TokenizerConfig,
+) -> Result<Vec<PartTokens>, TokenizerError> {
+    let config = dialect_config(Dialect::Dynpro, config)?;
+    dynpros
+        .iter()
+        .map(|dynpro| {
+            let source = SourceText::decode(dynpro.flow_logic.as_bytes(), Some(Encoding::Utf8))?;
+            let tokens = FlexibleTokenizer::new(&source.text, config.clone()).tokenize()?;
+            Ok(PartTokens {
+                part: Some(format!("{}{}", SCREEN_PART_PREFIX, dynpro.number)),
+                path: path.to_path_buf(),
+                dialect: Some(Dialect::Dynpro),
+                source,
+                tokens,
+            })
+        })
+        .collect()
+}
+
+/// Loads the built-in profile of a dialect, reading sources in the encoding
+/// of the ABAP configuration.
+fn dialect_config(
+    dialect: Dialect,
+    config: &TokenizerConfig,
+) -> Result<TokenizerConfig, TokenizerError> {
+    let mut dialect_config = dialect.config()?;
+    dialect_config.metadata.encoding = config.metadata.encoding;
+    Ok(dialect_config)
+}
+
+/// Recursively collects the abapGit files below `path`.
+fn collect_files(
+    path: &Path,
+    files: &mut Vec<(AbapGitFile, PathBuf)>,
+) -> Result<(), TokenizerError> {
+    let io_error =
+        |e: std::io::Error| TokenizerError::IoError(format!("{}: {}", path.display(), e));
+    for entry in fs::read_dir(path).map_err(io_error)? {
+        let entry_path = entry.map_err(io_error)?.path();
+        if entry_path.is_dir() {
+            collect_files(&entry_path, files)?;
+        } else if let Some(file) = AbapGitFile::parse(&entry_path) {
+            files.push((file, entry_path));
+        }
+    }
+    Ok(())
+}
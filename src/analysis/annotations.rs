@@ -0,0 +1,223 @@
+// abap-tokenizer/src/analysis/annotations.rs
+//! Pragmas and pseudo-comments as structured annotations.
+//!
+//! Pragmas (`##NEEDED`, `##SHADOW[param]`) and pseudo-comments
+//! (`"#EC CI_NOWHERE`) tell the syntax check and code inspections to ignore a
+//! finding in a statement. This module parses them into `Annotation` values
+//! and attaches each one to the statement it applies to, so linters can honor
+//! the same suppressions.
+use crate::config::TokenizerConfig;
+use crate::encoding::read_source;
+use crate::error::TokenizerError;
+use crate::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use crate::tokenizer::token::{Span, Token};
+use std::path::Path;
+
+/// Category of pragma tokens.
+pub const PRAGMA: &str = "Pragma";
+/// Prefix of a pragma.
+const PRAGMA_PREFIX: &str = "##";
+/// Prefix of a pseudo-comment.
+const PSEUDO_COMMENT_PREFIX: &str = "\"#EC";
+
+/// The kind of an annotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationKind {
+    /// A pragma such as `##NEEDED`.
+    Pragma,
+    /// A pseudo-comment such as `"#EC CI_NOWHERE`.
+    PseudoComment,
+}
+
+/// A pragma or pseudo-comment code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    /// The kind of the annotation.
+    pub kind: AnnotationKind,
+    /// The code, in upper case and without prefix, such as `NEEDED` or `CI_NOWHERE`.
+    pub code: String,
+    /// The parameters of a pragma, such as `param` in `##SHADOW[param]`.
+    pub parameters: Vec<String>,
+    /// The byte range of the pragma or pseudo-comment in the input.
+    pub span: Span,
+    /// The line number where the annotation appears.
+    pub line: usize,
+    /// The column number where the annotation starts.
+    pub column: usize,
+}
+
+/// A statement with the annotations that apply to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotatedStatement {
+    /// The byte range of the statement, from its first token to its period.
+    pub span: Span,
+    /// The line number where the statement starts.
+    pub line: usize,
+    /// The column number where the statement starts.
+    pub column: usize,
+    /// The annotations, in source order.
+    pub annotations: Vec<Annotation>,
+}
+
+/// The annotated statements of a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileAnnotations {
+    /// The path of the file.
+    pub file: String,
+    /// The statements with at least one annotation, in source order.
+    pub statements: Vec<AnnotatedStatement>,
+}
+
+impl AnnotatedStatement {
+    /// Returns true if the statement is annotated with the given code.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The pragma or pseudo-comment code, without prefix
+    pub fn has_code(&self, code: &str) -> bool {
+        self.annotations
+            .iter()
+            .any(|annotation| annotation.code.eq_ignore_ascii_case(code))
+    }
+}
+
+/// Parses the annotations of a pragma or pseudo-comment token.
+///
+/// A pseudo-comment can list several codes, each giving one annotation.
+///
+/// # Arguments
+///
+/// * `token` - A token produced by the tokenizer
+///
+/// # Returns
+///
+/// The annotations of the token, empty if it is neither a pragma nor a
+/// pseudo-comment.
+pub fn parse_annotations(token: &Token) -> Vec<Annotation> {
+    let annotation = |kind, code: &str, parameters| Annotation {
+        kind,
+        code: code.to_uppercase(),
+        parameters,
+        span: token.span,
+        line: token.line,
+        column: token.column,
+    };
+    let value = token.value.trim_end();
+    if token.token_type.category == PRAGMA {
+        let Some(body) = value.strip_prefix(PRAGMA_PREFIX) else {
+            return Vec::new();
+        };
+        let (code, rest) = body.split_at(body.find('[').unwrap_or(body.len()));
+        let parameters = rest
+            .split(['[', ']'])
+            .filter(|parameter| !parameter.is_empty())
+            .map(String::from)
+            .collect();
+        return vec![annotation(AnnotationKind::Pragma, code, parameters)];
+    }
+    if token.token_type.category == "Comment" {
+        let upper = value.to_uppercase();
+        if let Some(codes) = upper.strip_prefix(PSEUDO_COMMENT_PREFIX) {
+            return codes
+                .split_whitespace()
+                .map(|code| annotation(AnnotationKind::PseudoComment, code, Vec::new()))
+                .collect();
+        }
+    }
+    Vec::new()
+}
+
+/// Attaches the annotations in a token list to their statements.
+///
+/// A pragma or pseudo-comment applies to the statement it appears in. A
+/// pseudo-comment that follows the period of a statement on the same line
+/// applies to the statement it follows, as in
+/// `SELECT * FROM sflight INTO TABLE lt_f. "#EC CI_NOWHERE`. A pragma there
+/// applies to the next statement, as pragmas are written before the period
+/// of the statement they belong to.
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens of a complete source, in source order
+///
+/// # Returns
+///
+/// The statements with at least one annotation, in source order.
+pub fn find_annotations(tokens: &[Token]) -> Vec<AnnotatedStatement> {
+    let mut statements: Vec<AnnotatedStatement> = Vec::new();
+    // Annotations of the statement in progress
+    let mut current: Option<AnnotatedStatement> = None;
+    // Line of the period ending the previous statement
+    let mut previous_end_line = None;
+
+    for token in tokens {
+        let annotations = parse_annotations(token);
+        if !annotations.is_empty() {
+            let follows_previous = annotations[0].kind == AnnotationKind::PseudoComment
+                && current.is_none()
+                && previous_end_line == Some(token.line);
+            match statements.last_mut() {
+                Some(previous) if follows_previous => previous.annotations.extend(annotations),
+                _ => current
+                    .get_or_insert_with(|| statement_at(token, Vec::new()))
+                    .annotations
+                    .extend(annotations),
+            }
+            continue;
+        }
+        if token.is_comment() {
+            continue;
+        }
+        let statement = current.get_or_insert_with(|| statement_at(token, Vec::new()));
+        statement.span.end = token.span.end;
+        if token.symbol() == "." {
+            previous_end_line = Some(token.line);
+            if let Some(statement) = current.take() {
+                statements.push(statement);
+            }
+        }
+    }
+    statements.extend(current);
+    statements.retain(|statement| !statement.annotations.is_empty());
+    statements
+}
+
+/// Starts a statement at a token.
+fn statement_at(token: &Token, annotations: Vec<Annotation>) -> AnnotatedStatement {
+    AnnotatedStatement {
+        span: token.span,
+        line: token.line,
+        column: token.column,
+        annotations,
+    }
+}
+
+/// Tokenizes a source file and lists its annotated statements.
+///
+/// # Arguments
+///
+/// * `path` - The path of the ABAP source file
+/// * `config` - The TokenizerConfig to use for tokenization
+///
+/// # Returns
+///
+/// * `Result<FileAnnotations, TokenizerError>` - The annotated statements of
+///   the file, or an error if the file cannot be read or tokenized.
+pub fn annotations_in_file(
+    path: &str,
+    config: TokenizerConfig,
+) -> Result<FileAnnotations, TokenizerError> {
+    let source = read_source(Path::new(path), config.metadata.encoding)?;
+    let tokens = FlexibleTokenizer::new(&source.text, config).tokenize()?;
+    let mut statements = find_annotations(&tokens);
+    for statement in &mut statements {
+        statement.span = source.original_span(statement.span);
+        for annotation in &mut statement.annotations {
+            annotation.span = source.original_span(annotation.span);
+        }
+    }
+    Ok(FileAnnotations {
+        file: path.to_string(),
+        statements,
+    })
+}
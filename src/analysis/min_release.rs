@@ -0,0 +1,388 @@
+// abap-tokenizer/src/analysis/min_release.rs
+//! Detection of the minimum ABAP release a source requires.
+//!
+//! Newer releases add syntax that older systems reject, such as inline
+//! declarations or constructor expressions. This module finds those features
+//! in a token stream and derives the oldest release that supports all of
+//! them, together with the locations that pin it.
+//!
+//! Features are recognised from token values rather than subcategories, so
+//! that sources can be tokenized with any profile; the newest profile
+//! (`Dialect::Abap75x`) recognises every construct as a single token.
+use crate::analysis::annotations::PRAGMA;
+use crate::config::tokenizer_config::MAIN_MODE;
+use crate::config::TokenizerConfig;
+use crate::encoding::read_source;
+use crate::error::TokenizerError;
+use crate::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use crate::tokenizer::string_template::TEMPLATE_START;
+use crate::tokenizer::token::{Span, Token};
+use std::fmt;
+use std::path::Path;
+
+/// Constructor operators available since 7.40 SP02.
+const CONSTRUCTOR_OPERATORS: &[&str] = &[
+    "VALUE", "NEW", "CONV", "CAST", "REF", "EXACT", "COND", "SWITCH",
+];
+/// Constructor operators of iteration expressions, available since 7.40 SP08.
+const ITERATION_OPERATORS: &[&str] = &["REDUCE", "FILTER"];
+/// The component mapping operator, available since 7.40 SP05.
+const CORRESPONDING: &str = "CORRESPONDING";
+/// SQL functions available in Open SQL since 7.40 SP08.
+const SQL_FUNCTIONS: &[&str] = &[
+    "ABS", "CEIL", "FLOOR", "DIV", "MOD", "DIVISION", "ROUND", "CONCAT", "LPAD", "LENGTH",
+    "LTRIM", "REPLACE", "RIGHT", "RTRIM", "SUBSTRING", "COALESCE", "CAST",
+];
+/// SQL functions available in Open SQL since 7.50.
+const SQL_FUNCTIONS_750: &[&str] = &[
+    "CONCAT_WITH_SPACE", "INSTR", "LEFT", "LOWER", "UPPER", "RPAD",
+];
+/// Statements whose operands are Open SQL.
+const SQL_STATEMENTS: &[&str] = &["SELECT", "OPEN", "INSERT", "UPDATE", "MODIFY", "DELETE", "WITH"];
+
+/// An ABAP release, such as 7.40 SP08.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Release {
+    /// The major version, such as 7.
+    pub major: u8,
+    /// The minor version, such as 40.
+    pub minor: u8,
+    /// The support package, for features introduced within a release.
+    pub support_package: Option<u8>,
+}
+
+impl Release {
+    /// Creates a release without support package.
+    pub const fn new(major: u8, minor: u8) -> Self {
+        Release { major, minor, support_package: None }
+    }
+
+    /// Creates a release with a support package.
+    pub const fn with_support_package(major: u8, minor: u8, support_package: u8) -> Self {
+        Release { major, minor, support_package: Some(support_package) }
+    }
+}
+
+impl fmt::Display for Release {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:02}", self.major, self.minor)?;
+        if let Some(support_package) = self.support_package {
+            write!(f, " SP{:02}", support_package)?;
+        }
+        Ok(())
+    }
+}
+
+/// A syntax feature tied to the release that introduced it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    /// Pragmas such as `##NEEDED`.
+    Pragma,
+    /// String templates `|...|`.
+    StringTemplate,
+    /// The concatenation operator `&&`.
+    Concatenation,
+    /// Inline declarations `DATA(...)` and `FIELD-SYMBOL(...)`.
+    InlineDeclaration,
+    /// Constructor expressions such as `VALUE #( )` or `NEW lcl( )`.
+    ConstructorExpression,
+    /// Table expressions `itab[ ... ]`.
+    TableExpression,
+    /// Host variables `@var` in Open SQL.
+    HostVariable,
+    /// `CORRESPONDING #( )`.
+    Corresponding,
+    /// AMDP methods implemented in SQLScript.
+    Amdp,
+    /// `REDUCE` and `FILTER` expressions.
+    IterationExpression,
+    /// Inline declarations `@DATA(...)` in Open SQL.
+    HostInlineDeclaration,
+    /// SQL functions such as `CONCAT( )` or `COALESCE( )` in Open SQL.
+    SqlFunction,
+    /// SQL functions such as `UPPER( )` or `INSTR( )` added in 7.50.
+    SqlFunction750,
+    /// Host expressions `@( ... )` in Open SQL.
+    HostExpression,
+    /// `IS INSTANCE OF` and `CASE TYPE OF`.
+    TypePredicate,
+    /// Enumerated types `BEGIN OF ENUM`.
+    Enumeration,
+    /// Immutable inline declarations `FINAL(...)`.
+    FinalDeclaration,
+}
+
+impl Feature {
+    /// Returns the release that introduced the feature.
+    pub fn release(&self) -> Release {
+        match self {
+            Feature::Pragma | Feature::StringTemplate | Feature::Concatenation => {
+                Release::new(7, 2)
+            }
+            Feature::InlineDeclaration
+            | Feature::ConstructorExpression
+            | Feature::TableExpression => Release::with_support_package(7, 40, 2),
+            Feature::HostVariable | Feature::Corresponding | Feature::Amdp => {
+                Release::with_support_package(7, 40, 5)
+            }
+            Feature::IterationExpression
+            | Feature::HostInlineDeclaration
+            | Feature::SqlFunction => Release::with_support_package(7, 40, 8),
+            Feature::SqlFunction750 | Feature::HostExpression | Feature::TypePredicate => {
+                Release::new(7, 50)
+            }
+            Feature::Enumeration => Release::new(7, 51),
+            Feature::FinalDeclaration => Release::new(7, 57),
+        }
+    }
+
+    /// Returns a short description of the feature.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Feature::Pragma => "pragma",
+            Feature::StringTemplate => "string template",
+            Feature::Concatenation => "concatenation operator &&",
+            Feature::InlineDeclaration => "inline declaration",
+            Feature::ConstructorExpression => "constructor expression",
+            Feature::TableExpression => "table expression",
+            Feature::HostVariable => "host variable",
+            Feature::Corresponding => "CORRESPONDING expression",
+            Feature::Amdp => "AMDP method",
+            Feature::IterationExpression => "REDUCE or FILTER expression",
+            Feature::HostInlineDeclaration => "inline declaration in Open SQL",
+            Feature::SqlFunction | Feature::SqlFunction750 => "SQL function",
+            Feature::HostExpression => "host expression",
+            Feature::TypePredicate => "type predicate",
+            Feature::Enumeration => "enumerated type",
+            Feature::FinalDeclaration => "FINAL declaration",
+        }
+    }
+}
+
+/// A use of a release-specific feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureUsage {
+    /// The feature used.
+    pub feature: Feature,
+    /// The token the feature was recognised at, such as `DATA` or `VALUE`.
+    pub text: String,
+    /// The line number where the usage appears.
+    pub line: usize,
+    /// The column number where the usage starts.
+    pub column: usize,
+    /// The byte range of the token the feature was recognised at.
+    pub span: Span,
+}
+
+/// The minimum release of a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRelease {
+    /// The path of the file.
+    pub file: String,
+    /// The minimum release, or None if the file uses no release-specific feature.
+    pub minimum: Option<Release>,
+    /// Every feature usage, in source order.
+    pub usages: Vec<FeatureUsage>,
+}
+
+impl FileRelease {
+    /// Returns the usages of the features that require the minimum release.
+    pub fn pinning(&self) -> impl Iterator<Item = &FeatureUsage> {
+        self.usages
+            .iter()
+            .filter(move |usage| Some(usage.feature.release()) == self.minimum)
+    }
+}
+
+/// A word of the token stream. Tokens made of several words, such as
+/// keyword phrases, contribute one word each.
+struct Word<'a> {
+    /// The word in upper case, or the symbol of a punctuation token.
+    text: String,
+    /// The token the word belongs to.
+    token: &'a Token,
+}
+
+/// Lists the release-specific features used in a token list.
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens of a complete source, in source order
+///
+/// # Returns
+///
+/// The feature usages, in source order.
+pub fn find_features(tokens: &[Token]) -> Vec<FeatureUsage> {
+    let words: Vec<Word> = tokens
+        .iter()
+        .filter(|token| token.mode == MAIN_MODE && !token.is_comment())
+        .flat_map(|token| {
+            let words: Vec<&str> = match token.symbol() {
+                "" => vec![""],
+                symbol => symbol.split_whitespace().collect(),
+            };
+            words.into_iter().map(move |word| Word {
+                text: word.to_uppercase(),
+                token,
+            })
+        })
+        .collect();
+
+    let mut usages = Vec::new();
+    let mut statement_start = None;
+    for index in 0..words.len() {
+        let word = &words[index];
+        if statement_start.is_none() {
+            statement_start = Some(word.text.clone());
+        }
+        if let Some(feature) = feature_at(&words, index, statement_start.as_deref()) {
+            usages.push(FeatureUsage {
+                feature,
+                text: word.token.value.clone(),
+                line: word.token.line,
+                column: word.token.column,
+                span: word.token.span,
+            });
+        }
+        if word.text == "." {
+            statement_start = None;
+        }
+    }
+    usages
+}
+
+/// Returns the minimum release required by a list of feature usages.
+///
+/// # Arguments
+///
+/// * `usages` - Feature usages, as returned by `find_features`
+///
+/// # Returns
+///
+/// The newest release among the features, or None if there are none.
+pub fn minimum_release(usages: &[FeatureUsage]) -> Option<Release> {
+    usages.iter().map(|usage| usage.feature.release()).max()
+}
+
+/// Tokenizes a source file and determines its minimum release.
+///
+/// # Arguments
+///
+/// * `path` - The path of the ABAP source file
+/// * `config` - The TokenizerConfig to use for tokenization
+///
+/// # Returns
+///
+/// * `Result<FileRelease, TokenizerError>` - The minimum release and feature
+///   usages of the file, or an error if the file cannot be read or tokenized.
+pub fn min_release_in_file(
+    path: &str,
+    config: TokenizerConfig,
+) -> Result<FileRelease, TokenizerError> {
+    let source = read_source(Path::new(path), config.metadata.encoding)?;
+    let tokens = FlexibleTokenizer::new(&source.text, config).tokenize()?;
+    let mut usages = find_features(&tokens);
+    for usage in &mut usages {
+        usage.span = source.original_span(usage.span);
+    }
+    Ok(FileRelease {
+        file: path.to_string(),
+        minimum: minimum_release(&usages),
+        usages,
+    })
+}
+
+/// Recognises the feature starting at a word, if any.
+fn feature_at(words: &[Word], index: usize, statement_start: Option<&str>) -> Option<Feature> {
+    let word = &words[index];
+    let text = |offset: usize| words.get(index + offset).map_or("", |word| word.text.as_str());
+    let attached = |offset: usize| {
+        match (words.get(index + offset - 1), words.get(index + offset)) {
+            (Some(before), Some(after)) => {
+                before.token.span.end == after.token.span.start
+                    && before.token.file_id == after.token.file_id
+            }
+            _ => false,
+        }
+    };
+    let previous = index.checked_sub(1).map(|previous| &words[previous]);
+
+    match word.token.token_type.category.as_str() {
+        PRAGMA => return Some(Feature::Pragma),
+        TEMPLATE_START => return Some(Feature::StringTemplate),
+        _ => {}
+    }
+    let after_at = previous.is_some_and(|previous| previous.text == "@");
+    match word.text.as_str() {
+        "&&" => Some(Feature::Concatenation),
+        "@" if text(1) == "(" && attached(1) => Some(Feature::HostExpression),
+        "@" if text(1) == "DATA" && text(2) == "(" && attached(1) && attached(2) => {
+            Some(Feature::HostInlineDeclaration)
+        }
+        "@" if text(1) == "FINAL" && text(2) == "(" && attached(1) && attached(2) => {
+            Some(Feature::FinalDeclaration)
+        }
+        "@" if attached(1) => Some(Feature::HostVariable),
+        "DATA" if !after_at && text(1) == "(" && attached(1) => Some(Feature::InlineDeclaration),
+        "FINAL" if !after_at && text(1) == "(" && attached(1) => Some(Feature::FinalDeclaration),
+        "FIELD" if text(1) == "-"
+            && text(2) == "SYMBOL"
+            && text(3) == "("
+            && attached(1)
+            && attached(2)
+            && attached(3) =>
+        {
+            Some(Feature::InlineDeclaration)
+        }
+        "[" if text(1) != "]"
+            && attached(0)
+            && previous.is_some_and(|previous| {
+                previous.text == ")" || previous.text == "]" || is_name(&previous.text)
+            }) =>
+        {
+            Some(Feature::TableExpression)
+        }
+        "IS" if text(1) == "INSTANCE" && text(2) == "OF" => Some(Feature::TypePredicate),
+        "CASE" if text(1) == "TYPE" && text(2) == "OF" => Some(Feature::TypePredicate),
+        "BEGIN" if text(1) == "OF" && text(2) == "ENUM" => Some(Feature::Enumeration),
+        "LANGUAGE" if text(1) == "SQLSCRIPT" => Some(Feature::Amdp),
+        operator if is_constructor(text(1), text(2), attached(2)) => {
+            if operator == CORRESPONDING {
+                Some(Feature::Corresponding)
+            } else if ITERATION_OPERATORS.contains(&operator) {
+                Some(Feature::IterationExpression)
+            } else if CONSTRUCTOR_OPERATORS.contains(&operator) {
+                Some(Feature::ConstructorExpression)
+            } else {
+                None
+            }
+        }
+        function
+            if text(1) == "("
+                && attached(1)
+                && statement_start.is_some_and(|start| SQL_STATEMENTS.contains(&start)) =>
+        {
+            if SQL_FUNCTIONS.contains(&function) {
+                Some(Feature::SqlFunction)
+            } else if SQL_FUNCTIONS_750.contains(&function) {
+                Some(Feature::SqlFunction750)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns true if the words after an operator form the `#(` or `type(` of a
+/// constructor expression.
+fn is_constructor(type_word: &str, bracket: &str, bracket_attached: bool) -> bool {
+    (type_word == "#" || is_name(type_word)) && bracket == "(" && bracket_attached
+}
+
+/// Returns true if a word can be the name of a data object or type.
+fn is_name(word: &str) -> bool {
+    word.chars()
+        .next()
+        .is_some_and(|ch| ch.is_alphabetic() || ch == '_' || ch == '/')
+}
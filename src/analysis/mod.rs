@@ -0,0 +1,14 @@
+// abap-tokenizer/src/analysis/mod.rs
+//! Analyses built on top of the token stream.
+//!
+//! Each submodule extracts one kind of information from tokenized ABAP code,
+//! such as the text symbols a program uses.
+
+/// Inventory of text symbol usages.
+pub mod text_symbols;
+
+/// Pragmas and pseudo-comments attached to their statements.
+pub mod annotations;
+
+/// Minimum ABAP release required by a source.
+pub mod min_release;
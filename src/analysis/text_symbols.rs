@@ -0,0 +1,132 @@
+// abap-tokenizer/src/analysis/text_symbols.rs
+//! Inventory of text symbol usages.
+//!
+//! Text symbols are translatable texts identified by a three-character key.
+//! ABAP code uses them either directly, as `TEXT-001`, or as the fallback key
+//! of a literal, as `'Hello'(001)`. The tokenizer produces `Identifier:TextSymbol`
+//! and `Literal:TextSymbol` tokens for those forms; this module lists them.
+use crate::config::TokenizerConfig;
+use crate::encoding::read_source;
+use crate::error::TokenizerError;
+use crate::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use crate::tokenizer::token::{Span, Token};
+use std::path::Path;
+
+/// Subcategory of the tokens using a text symbol.
+pub const TEXT_SYMBOL: &str = "TextSymbol";
+
+/// The form in which a text symbol is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSymbolKind {
+    /// A reference such as `TEXT-001`.
+    Reference,
+    /// A literal with a text symbol key such as `'Hello'(001)`.
+    Literal,
+}
+
+/// A single use of a text symbol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextSymbolUsage {
+    /// The three-character key of the text symbol, in upper case.
+    pub key: String,
+    /// The form of the usage.
+    pub kind: TextSymbolKind,
+    /// The literal text used when the text symbol is not translated, decoded
+    /// as in `Token::decoded_value`. Only set for `Literal` usages.
+    pub text: Option<String>,
+    /// The line number where the usage appears.
+    pub line: usize,
+    /// The column number where the usage starts.
+    pub column: usize,
+    /// The byte range of the usage in the input.
+    pub span: Span,
+}
+
+/// The text symbol usages of a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileTextSymbols {
+    /// The path of the file.
+    pub file: String,
+    /// The usages, in source order.
+    pub usages: Vec<TextSymbolUsage>,
+}
+
+impl TextSymbolUsage {
+    /// Builds the usage for a token, if the token uses a text symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - A token produced by the tokenizer
+    ///
+    /// # Returns
+    ///
+    /// The usage, or None if the token is not a text symbol token.
+    pub fn from_token(token: &Token) -> Option<Self> {
+        if token.token_type.subcategory.as_deref() != Some(TEXT_SYMBOL) {
+            return None;
+        }
+        let (kind, key, text) = match token.token_type.category.as_str() {
+            "Identifier" => {
+                let (_, key) = token.value.split_once('-')?;
+                (TextSymbolKind::Reference, key, None)
+            }
+            "Literal" => {
+                let (_, key) = token.value.rsplit_once('(')?;
+                (
+                    TextSymbolKind::Literal,
+                    key.strip_suffix(')')?,
+                    token.decoded_value.clone(),
+                )
+            }
+            _ => return None,
+        };
+        Some(TextSymbolUsage {
+            key: key.to_uppercase(),
+            kind,
+            text,
+            line: token.line,
+            column: token.column,
+            span: token.span,
+        })
+    }
+}
+
+/// Lists the text symbol usages in a token list.
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens of a complete source, in source order
+///
+/// # Returns
+///
+/// The usages, in source order.
+pub fn find_text_symbols(tokens: &[Token]) -> Vec<TextSymbolUsage> {
+    tokens.iter().filter_map(TextSymbolUsage::from_token).collect()
+}
+
+/// Tokenizes a source file and lists its text symbol usages.
+///
+/// # Arguments
+///
+/// * `path` - The path of the ABAP source file
+/// * `config` - The TokenizerConfig to use for tokenization
+///
+/// # Returns
+///
+/// * `Result<FileTextSymbols, TokenizerError>` - The usages of the file, or an
+///   error if the file cannot be read or tokenized.
+pub fn text_symbols_in_file(
+    path: &str,
+    config: TokenizerConfig,
+) -> Result<FileTextSymbols, TokenizerError> {
+    let source = read_source(Path::new(path), config.metadata.encoding)?;
+    let tokens = FlexibleTokenizer::new(&source.text, config).tokenize()?;
+    let mut usages = find_text_symbols(&tokens);
+    for usage in &mut usages {
+        usage.span = source.original_span(usage.span);
+    }
+    Ok(FileTextSymbols {
+        file: path.to_string(),
+        usages,
+    })
+}
@@ -0,0 +1,231 @@
+// abap-tokenizer/src/config/dialect.rs
+//! Built-in configuration profiles per ABAP release.
+//!
+//! The crate ships a configuration for each supported release, embedded in
+//! the binary so that it works without the `config` directory. Each profile
+//! imports the profile of the previous release and adds its syntax:
+//!
+//! - 7.02 (up to 7.31): the base language, including string templates,
+//!   pragmas and native SQL.
+//! - 7.40: inline declarations, constructor operators such as `VALUE #( )`,
+//!   escaped host variables and AMDP methods in SQLScript.
+//! - 7.50: host expressions and `IS INSTANCE OF` / `CASE TYPE OF`.
+//! - 7.5x (7.51 to 7.58): enumerated types and `FINAL( )` declarations.
+//! - ABAP Cloud: the 7.5x language, with statements not allowed in ABAP
+//!   Cloud recognised as `Keyword:Forbidden`.
+//!
+//! The languages kept next to ABAP in a repository have profiles of their
+//! own: CDS data definitions (`Dialect::Cds`), RAP behavior definitions
+//! (`Dialect::Bdef`) and Dynpro flow logic (`Dialect::Dynpro`).
+//! `Dialect::for_file` picks them by file extension.
+use super::toml_loader::{load_config, ConfigSource};
+use super::TokenizerConfig;
+use crate::error::ConfigError;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The embedded configuration files, by path relative to the `config` directory.
+const EMBEDDED_CONFIGS: &[(&str, &str)] = &[
+    ("dialects/abap_702.toml", include_str!("../../config/dialects/abap_702.toml")),
+    ("dialects/abap_740.toml", include_str!("../../config/dialects/abap_740.toml")),
+    ("dialects/abap_750.toml", include_str!("../../config/dialects/abap_750.toml")),
+    ("dialects/abap_75x.toml", include_str!("../../config/dialects/abap_75x.toml")),
+    ("dialects/abap_cloud.toml", include_str!("../../config/dialects/abap_cloud.toml")),
+    ("cds.toml", include_str!("../../config/cds.toml")),
+    ("bdef.toml", include_str!("../../config/bdef.toml")),
+    ("dynpro.toml", include_str!("../../config/dynpro.toml")),
+    ("native_sql.toml", include_str!("../../config/native_sql.toml")),
+    ("sqlscript.toml", include_str!("../../config/sqlscript.toml")),
+];
+
+/// Extensions of CDS source files: data definitions, metadata extensions and
+/// access controls.
+const CDS_EXTENSIONS: &[&str] = &["asddls", "asddlxs", "asdcls"];
+/// Extension of behavior definition files.
+const BDEF_EXTENSION: &str = "asbdef";
+/// Part of the abapGit file name of a screen, as in `zfoo.prog.screen_0100.xml`.
+const SCREEN_PART: &str = ".screen_";
+/// Extension of screen files, whose flow logic is embedded in XML.
+const SCREEN_EXTENSION: &str = "xml";
+
+/// An ABAP release or source language with a built-in configuration profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum Dialect {
+    /// ABAP 7.02 to 7.31.
+    Abap702,
+    /// ABAP 7.40.
+    Abap740,
+    /// ABAP 7.50, the default.
+    #[default]
+    Abap750,
+    /// ABAP 7.51 to 7.58.
+    Abap75x,
+    /// ABAP Cloud.
+    Cloud,
+    /// ABAP CDS data definitions.
+    Cds,
+    /// RAP behavior definitions.
+    Bdef,
+    /// Dynpro flow logic.
+    Dynpro,
+}
+
+impl Dialect {
+    /// All dialects, from the oldest release to the newest, followed by the
+    /// other source languages.
+    pub const ALL: [Dialect; 8] = [
+        Dialect::Abap702,
+        Dialect::Abap740,
+        Dialect::Abap750,
+        Dialect::Abap75x,
+        Dialect::Cloud,
+        Dialect::Cds,
+        Dialect::Bdef,
+        Dialect::Dynpro,
+    ];
+
+    /// Returns the name of the dialect, as accepted by `from_str`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Dialect::Abap702 => "7.02",
+            Dialect::Abap740 => "7.40",
+            Dialect::Abap750 => "7.50",
+            Dialect::Abap75x => "7.5x",
+            Dialect::Cloud => "cloud",
+            Dialect::Cds => "cds",
+            Dialect::Bdef => "bdef",
+            Dialect::Dynpro => "dynpro",
+        }
+    }
+
+    /// Returns the path of the profile, relative to the `config` directory.
+    fn config_path(&self) -> &'static str {
+        match self {
+            Dialect::Abap702 => "dialects/abap_702.toml",
+            Dialect::Abap740 => "dialects/abap_740.toml",
+            Dialect::Abap750 => "dialects/abap_750.toml",
+            Dialect::Abap75x => "dialects/abap_75x.toml",
+            Dialect::Cloud => "dialects/abap_cloud.toml",
+            Dialect::Cds => "cds.toml",
+            Dialect::Bdef => "bdef.toml",
+            Dialect::Dynpro => "dynpro.toml",
+        }
+    }
+
+    /// Selects the dialect for a source file by its extension, such as the
+    /// CDS profile for `zi_flight.ddls.asddls`, the behavior definition
+    /// profile for `zi_flight.bdef.asbdef` or the flow logic profile for
+    /// `zfoo.prog.screen_0100.xml`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the source file
+    ///
+    /// # Returns
+    ///
+    /// The dialect of the file, or None if its extension does not determine
+    /// one, as for `.abap` files, whose release cannot be told from the name.
+    pub fn for_file(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        if CDS_EXTENSIONS.contains(&extension.as_str()) {
+            return Some(Dialect::Cds);
+        }
+        if extension == BDEF_EXTENSION {
+            return Some(Dialect::Bdef);
+        }
+        let file_name = path.file_name()?.to_str()?.to_lowercase();
+        if extension == SCREEN_EXTENSION && file_name.contains(SCREEN_PART) {
+            return Some(Dialect::Dynpro);
+        }
+        None
+    }
+
+    /// Selects the dialect for a `language_version`, such as `ABAP 7.5`,
+    /// `7.40 SP08`, `ABAP Cloud`, `ABAP CDS`, `ABAP BDEF` or
+    /// `Dynpro flow logic`.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The language version of a configuration
+    ///
+    /// # Returns
+    ///
+    /// The dialect of the release, or None for releases before 7.02 and
+    /// versions that name no release.
+    pub fn from_language_version(version: &str) -> Option<Self> {
+        let lowercase = version.to_lowercase();
+        if lowercase.contains("cloud") || lowercase.contains("steampunk") {
+            return Some(Dialect::Cloud);
+        }
+        if lowercase.contains("cds") {
+            return Some(Dialect::Cds);
+        }
+        if lowercase.contains("bdef") || lowercase.contains("behavior") {
+            return Some(Dialect::Bdef);
+        }
+        if lowercase.contains("dynpro") || lowercase.contains("flow logic") {
+            return Some(Dialect::Dynpro);
+        }
+        let start = version.find(|c: char| c.is_ascii_digit())?;
+        let number: String = version[start..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit() || *c == '.')
+            .collect();
+        let (major, minor) = number.split_once('.')?;
+        if major != "7" || minor.is_empty() {
+            return None;
+        }
+        // "7.5" is release 7.50, "7.02" is 7.02
+        let minor: u32 = format!("{:0<2}", minor).get(..2)?.parse().ok()?;
+        match minor {
+            2..=39 => Some(Dialect::Abap702),
+            40..=49 => Some(Dialect::Abap740),
+            50 => Some(Dialect::Abap750),
+            51..=59 => Some(Dialect::Abap75x),
+            _ => None,
+        }
+    }
+
+    /// Loads the built-in configuration of the dialect.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TokenizerConfig, ConfigError>` - The configuration, or an
+    ///   error if the embedded profile is invalid.
+    pub fn config(&self) -> Result<TokenizerConfig, ConfigError> {
+        load_config(Path::new(self.config_path()), ConfigSource::Embedded)
+    }
+}
+
+impl fmt::Display for Dialect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Dialect {
+    type Err = String;
+
+    /// Parses a dialect name, such as `7.40` or `cloud`, or any
+    /// `language_version` accepted by `from_language_version`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Dialect::ALL
+            .into_iter()
+            .find(|dialect| dialect.name().eq_ignore_ascii_case(name))
+            .or_else(|| Dialect::from_language_version(name))
+            .ok_or_else(|| format!("unknown dialect: {}", name))
+    }
+}
+
+/// Returns the content of an embedded configuration file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file relative to the `config` directory
+pub(crate) fn embedded_config(path: &Path) -> Option<&'static str> {
+    EMBEDDED_CONFIGS
+        .iter()
+        .find(|(name, _)| Path::new(name) == path)
+        .map(|(_, content)| *content)
+}
@@ -11,8 +11,14 @@ pub(crate) mod tokenizer_config;
 /// Handles the loading and parsing of TOML configuration files.
 mod toml_loader;
 
+/// Built-in configuration profiles per ABAP release.
+pub mod dialect;
+
 /// Re-exports the TokenizerConfig and CompiledPatternConfig structs for use in other modules.
 pub use tokenizer_config::{TokenizerConfig, CompiledPatternConfig};
 
+/// Re-exports the Dialect enum for selecting a built-in profile.
+pub use dialect::Dialect;
+
 /// Re-exports the load_toml_config function for loading TOML configuration files.
 pub use toml_loader::load_toml_config;
\ No newline at end of file
@@ -4,28 +4,52 @@
 //! This module defines the structures used to represent the tokenizer's configuration,
 //! both in its raw form (as loaded from TOML) and in its compiled form (ready for use
 //! by the tokenizer).
+use crate::encoding::Encoding;
 use crate::error::ConfigError;
-use regex::Regex;
+use regex::{Regex, RegexBuilder};
 use serde::Deserialize;
 use std::{any::Any, collections::HashMap};
 
 /// Raw configuration structure as loaded from TOML.
+///
+/// Only the metadata is required: a configuration importing others can
+/// consist of the rules it adds.
 #[derive(Deserialize)]
 pub struct RawTokenizerConfig {
     /// General metadata about the tokenizer configuration.
     pub metadata: Metadata,
     /// Definitions of token categories and their properties.
+    #[serde(default)]
     pub token_categories: HashMap<String, CategoryConfig>,
     /// Raw pattern definitions for each token category.
+    #[serde(default)]
     pub patterns: HashMap<String, Vec<RawPatternConfig>>,
     /// Rules for handling context-sensitive tokens.
+    #[serde(default)]
     pub context_rules: HashMap<String, ContextRule>,
     /// Definitions of custom actions to be taken for certain token types.
+    #[serde(default)]
     pub custom_actions: HashMap<String, CustomAction>,
-    /// Optional list of configuration files to import.
+    /// Optional list of configuration files to import, relative to this file.
     pub imports: Option<Vec<String>>,
+    /// Optional built-in profile to build on, named as with `--dialect`
+    /// (see `Dialect`). It is merged after the imports.
+    pub profile: Option<String>,
     /// Special rules for handling specific token patterns.
+    #[serde(default)]
     pub special_rules: Vec<SpecialRule>,
+    /// Line and block comment rules.
+    #[serde(default)]
+    pub comments: Vec<CommentRule>,
+    /// Rules reclassifying keywords used as identifiers.
+    #[serde(default)]
+    pub contextual_rules: Vec<ContextualRule>,
+    /// Keyword sequences matched across whitespace and comments.
+    #[serde(default)]
+    pub phrases: Vec<RawPhraseConfig>,
+    /// Additional lexer modes with their own rule sets, by name.
+    #[serde(default)]
+    pub modes: HashMap<String, RawModeConfig>,
 }
 
 
@@ -36,18 +60,127 @@ pub struct RawPatternConfig {
     pub regex: String,
     /// Optional subcategory for finer-grained token classification.
     pub subcategory: Option<String>,
+    /// Optional regex the input must match right after the token.
+    /// The text it matches is not part of the token.
+    pub followed_by: Option<String>,
+    /// Whether the token must touch non-whitespace text on both sides.
+    pub attached: Option<bool>,
+    /// Token sequences of which one must immediately precede the token, such
+    /// as `["TYPE", "TYPE REF TO"]`. See `TokenizerConfig::is_preceded_by`.
+    pub after: Option<Vec<String>>,
+    /// Lexer mode to enter after a token matching this pattern.
+    pub push: Option<String>,
+    /// Token value delaying the `push` until a token with this value is produced.
+    pub push_after: Option<String>,
+    /// Whether to return to the previous lexer mode after a matching token.
+    pub pop: Option<bool>,
+    /// Overrides `metadata.case_sensitive` for this pattern and its
+    /// `followed_by` regex, e.g. for case-sensitive annotation names in an
+    /// otherwise case-insensitive language.
+    pub case_sensitive: Option<bool>,
 }
 
 /// Compiled pattern configuration ready for use by the tokenizer.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CompiledPatternConfig {
     /// Compiled regular expression for efficient token matching.
     pub regex: Regex,
     /// Optional subcategory for finer-grained token classification.
     pub subcategory: Option<String>,
+    /// Optional compiled regex the input must match right after the token.
+    pub followed_by: Option<Regex>,
+    /// Whether the token must touch non-whitespace text on both sides.
+    pub attached: bool,
+    /// Token sequences of which one must immediately precede the token.
+    pub after: Option<Vec<String>>,
+    /// Lexer mode to enter after a token matching this pattern.
+    pub push: Option<String>,
+    /// Token value delaying the `push` until a token with this value is produced.
+    pub push_after: Option<String>,
+    /// Whether to return to the previous lexer mode after a matching token.
+    pub pop: bool,
+}
+
+/// Raw lexer mode as defined in TOML under `[modes.<name>]`.
+#[derive(Deserialize)]
+pub struct RawModeConfig {
+    /// Definitions of token categories and their properties in this mode.
+    #[serde(default)]
+    pub token_categories: HashMap<String, CategoryConfig>,
+    /// Raw pattern definitions for each token category in this mode.
+    #[serde(default)]
+    pub patterns: HashMap<String, Vec<RawPatternConfig>>,
+    /// Special rules for handling specific token patterns in this mode.
+    #[serde(default)]
+    pub special_rules: Vec<SpecialRule>,
+    /// Comment rules of this mode.
+    #[serde(default)]
+    pub comments: Vec<CommentRule>,
+    /// Optional path of a configuration file providing the rules of this mode,
+    /// relative to the file declaring the mode.
+    pub config: Option<String>,
+    /// Optional regex marking the end of the mode. The matching text is not
+    /// consumed: the mode is left and the text is lexed by the previous mode.
+    pub end: Option<String>,
+    /// Optional category for the region. When the mode has no rules of its own,
+    /// the text up to `end` becomes a single token of this category.
+    pub region: Option<String>,
+}
+
+/// Compiled lexer mode ready for use by the tokenizer.
+///
+/// A mode replaces the categories, patterns and special rules of the main
+/// configuration while it is active. Modes are entered and left through the
+/// `push` and `pop` attributes of patterns and special rules.
+#[derive(Clone)]
+pub struct ModeConfig {
+    /// Definitions of token categories and their properties in this mode.
+    pub token_categories: HashMap<String, CategoryConfig>,
+    /// Compiled pattern definitions for each token category in this mode.
+    pub patterns: HashMap<String, Vec<CompiledPatternConfig>>,
+    /// Special rules for handling specific token patterns in this mode.
+    pub special_rules: Vec<SpecialRule>,
+    /// Comment rules of this mode.
+    pub comments: Vec<CommentRule>,
+    /// Optional path of a configuration file providing the rules of this mode.
+    pub config: Option<String>,
+    /// Optional compiled regex marking the end of the mode.
+    pub end: Option<Regex>,
+    /// The `end` regex anchored at the start of the input, used to check
+    /// whether the mode ends at the next token.
+    pub end_at_start: Option<Regex>,
+    /// Category of the region; defaults to the mode name.
+    pub region: Option<String>,
+}
+
+impl ModeConfig {
+    /// Returns true if the mode has no rules of its own, so that its whole
+    /// content up to `end` is emitted as a single region token.
+    pub fn is_opaque(&self) -> bool {
+        self.patterns.is_empty() && self.special_rules.is_empty() && self.comments.is_empty()
+    }
+
+    /// Replaces the rules of this mode with those of another configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The configuration whose top-level rules the mode should use
+    pub fn use_rules_of(&mut self, other: TokenizerConfig) {
+        self.token_categories = other.token_categories;
+        self.patterns = other.patterns;
+        self.special_rules = other.special_rules;
+        self.comments = other.comments;
+    }
 }
 
+/// Name of the mode formed by the top-level rules of a configuration.
+pub const MAIN_MODE: &str = "main";
+
+/// Distance between tab stops when `tab_width` is not configured.
+pub const DEFAULT_TAB_WIDTH: usize = 8;
+
 /// Main tokenizer configuration structure.
+#[derive(Clone)]
 pub struct TokenizerConfig {
     /// General metadata about the tokenizer configuration.
     pub metadata: Metadata,
@@ -63,21 +196,47 @@ pub struct TokenizerConfig {
     pub imports: Option<Vec<String>>,
     /// Special rules for handling specific token patterns.
     pub special_rules: Vec<SpecialRule>,
+    /// Line and block comment rules, in configuration order.
+    pub comments: Vec<CommentRule>,
+    /// Rules reclassifying keywords used as identifiers.
+    pub contextual_rules: Vec<ContextualRule>,
+    /// Compiled keyword phrases, longest first.
+    pub phrases: Vec<Phrase>,
+    /// Additional lexer modes, by name.
+    pub modes: HashMap<String, ModeConfig>,
 }
 
 /// Metadata for the tokenizer configuration.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct Metadata {
     /// Version of the ABAP language this configuration targets.
     pub language_version: String,
-    /// Whether the tokenizer should be case-sensitive.
+    /// Whether the tokenizer should be case-sensitive. When false, patterns
+    /// are compiled case-insensitively, so that a lower-case `type` is lexed
+    /// as the keyword `TYPE` and can then be reclassified as a name by the
+    /// contextual rules.
     pub case_sensitive: bool,
     /// Whether to allow Unicode characters in identifiers.
     pub allow_unicode_identifiers: bool,
+    /// Distance between tab stops, used for visual columns. Defaults to
+    /// `DEFAULT_TAB_WIDTH`.
+    #[serde(default = "Metadata::default_tab_width")]
+    pub tab_width: usize,
+    /// Encoding of the source files read with this configuration, UTF-8 if
+    /// not given. A byte order mark in a file takes precedence.
+    #[serde(default)]
+    pub encoding: Option<Encoding>,
+}
+
+impl Metadata {
+    /// Returns the tab width used when the configuration does not set one.
+    fn default_tab_width() -> usize {
+        DEFAULT_TAB_WIDTH
+    }
 }
 
 /// Configuration for a token category.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct CategoryConfig {
     /// Priority of the category for resolving conflicts.
     pub priority: u32,
@@ -86,7 +245,7 @@ pub struct CategoryConfig {
 }
 
 /// Rule for handling context-sensitive tokens.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone)]
 pub struct ContextRule {
     /// String that marks the start of the context.
     pub start: String,
@@ -98,8 +257,68 @@ pub struct ContextRule {
     pub multiline: Option<bool>,
 }
 
-/// Definition of a custom action for specific token types.
+/// Rule reclassifying a keyword-shaped token as an identifier.
+///
+/// ABAP allows most keywords to be used as names. A contextual rule describes
+/// the position in which a token matched by a `Keyword` pattern is actually a
+/// name. All conditions present in the rule must hold for it to apply.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ContextualRule {
+    /// Name of the rule, used for debugging output.
+    pub name: String,
+    /// Token sequences that must immediately precede the token. Each entry is
+    /// a whitespace separated list of token values, e.g. `"DATA ("`.
+    pub after: Option<Vec<String>>,
+    /// Token values of which one must immediately follow the token.
+    pub before: Option<Vec<String>>,
+    /// Whether the token must be written directly after the previous token,
+    /// without whitespace in between.
+    pub adjacent: Option<bool>,
+    /// Keywords of which one must start the statement containing the token.
+    pub statement_starts: Option<Vec<String>>,
+    /// Whether the token must (or must not) be enclosed in parentheses.
+    pub in_parentheses: Option<bool>,
+    /// Optional subcategory for the resulting identifier token.
+    pub subcategory: Option<String>,
+}
+
+/// Raw keyword phrase as defined in TOML.
 #[derive(Debug, Deserialize)]
+pub struct RawPhraseConfig {
+    /// The words of the phrase, e.g. `["ORDER", "BY"]`.
+    pub words: Vec<String>,
+    /// Category of the emitted tokens. Defaults to `Keyword`.
+    pub category: Option<String>,
+    /// Optional subcategory of the emitted tokens.
+    pub subcategory: Option<String>,
+    /// How the phrase is emitted: `"composite"` (default) or `"members"`.
+    pub emit: Option<String>,
+}
+
+/// How a matched phrase is turned into tokens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhraseEmit {
+    /// A single token covering the whole phrase.
+    Composite,
+    /// One token per word, all sharing the same phrase id.
+    Members,
+}
+
+/// Compiled keyword phrase ready for use by the tokenizer.
+#[derive(Debug, Clone)]
+pub struct Phrase {
+    /// The words of the phrase, upper-cased unless the configuration is case-sensitive.
+    pub words: Vec<String>,
+    /// Category of the emitted tokens.
+    pub category: String,
+    /// Optional subcategory of the emitted tokens.
+    pub subcategory: Option<String>,
+    /// How the phrase is emitted.
+    pub emit: PhraseEmit,
+}
+
+/// Definition of a custom action for specific token types.
+#[derive(Debug, Deserialize, Clone)]
 pub struct CustomAction {
     /// Name of the action to be performed.
     pub action: String,
@@ -107,7 +326,68 @@ pub struct CustomAction {
     pub args: Option<HashMap<String, String>>,
 }
 
+/// Token type of comments when a comment rule does not set one.
+pub const COMMENT: &str = "Comment";
+
+/// Rule recognising a comment, as defined in TOML under `[[comments]]`.
+///
+/// A rule without `end` describes a line comment, which extends to the end of
+/// the line, including the line break. A rule with `end` describes a block
+/// comment, which may span several lines; an unterminated block comment
+/// extends to the end of the input and is reported as a diagnostic.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CommentRule {
+    /// String that starts the comment, such as `"` or `/*`.
+    pub start: String,
+    /// String that ends a block comment, such as `*/`.
+    pub end: Option<String>,
+    /// Whether block comments nest, so that every `start` inside the comment
+    /// needs its own `end`.
+    #[serde(default)]
+    pub nested: bool,
+    /// Optional visual column where the comment must start, as for the `*`
+    /// comments of ABAP.
+    pub start_column: Option<usize>,
+    /// Type of the comment tokens, such as `DocComment`. Defaults to `Comment`.
+    #[serde(default = "CommentRule::default_token_type")]
+    pub token_type: String,
+}
+
+impl CommentRule {
+    /// Returns the token type used when the rule does not set one.
+    fn default_token_type() -> String {
+        COMMENT.to_string()
+    }
+
+    /// Returns true if the rule describes a block comment.
+    pub fn is_block(&self) -> bool {
+        self.end.is_some()
+    }
+
+    /// Checks that the rule can match.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), ConfigError>` - Ok if the rule is valid, Err otherwise
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.start.is_empty() || self.end.as_ref().is_some_and(String::is_empty) {
+            return Err(ConfigError::ConfigurationError(format!(
+                "Comment rule with empty delimiter: {:?}",
+                self.start
+            )));
+        }
+        if self.nested && !self.is_block() {
+            return Err(ConfigError::ConfigurationError(format!(
+                "Line comment rule {:?} cannot be nested",
+                self.start
+            )));
+        }
+        Ok(())
+    }
+}
+
 /// Raw representation of a special rule.
+#[allow(dead_code)]
 #[derive(Deserialize, Clone)]
 pub struct RawSpecialRule {
     /// Name of the special rule.
@@ -116,12 +396,14 @@ pub struct RawSpecialRule {
     pub start: String,
     /// Optional string that marks the end of the special pattern.
     pub end: Option<String>,
-    /// Optional column where the pattern must start to be valid.
+    /// Optional visual column where the pattern must start to be valid.
     pub start_column: Option<usize>,
     /// Optional minimum length for the pattern to be valid.
     pub min_length: Option<usize>,
     /// Optional regex for additional pattern matching.
     pub regex: Option<String>,
+    /// Optional token sequences of which one must immediately precede the match.
+    pub after: Option<Vec<String>>,
     /// Type of token to be created when this rule matches.
     pub token_type: String,
 }
@@ -133,20 +415,35 @@ pub struct SpecialRule {
     pub start: String,
     /// Optional string that marks the end of the special pattern.
     pub end: Option<String>,
-    /// Optional column where the pattern must start to be valid.
+    /// Optional visual column where the pattern must start to be valid; see the
+    /// column model in `tokenizer::token`.
     pub start_column: Option<usize>,
     /// Optional minimum length for the pattern to be valid.
     pub min_length: Option<usize>,
     /// Optional regex for additional pattern matching.
     pub regex: Option<String>,
+    /// Optional token sequences of which one must immediately precede the
+    /// match. See `TokenizerConfig::is_preceded_by`.
+    pub after: Option<Vec<String>>,
     /// Type of token to be created when this rule matches.
     pub token_type: String,
+    /// Lexer mode to enter after a token matching this rule.
+    pub push: Option<String>,
+    /// Token value delaying the `push` until a token with this value is produced.
+    pub push_after: Option<String>,
+    /// Whether to return to the previous lexer mode after a matching token.
+    pub pop: Option<bool>,
 }
 
 impl TokenizerConfig {
     /// Merges another TokenizerConfig into this one.
     ///
-    /// This method is useful for combining multiple configuration files.
+    /// This method is useful for combining multiple configuration files. The
+    /// definitions of this configuration take precedence: its patterns,
+    /// special rules, comment rules and phrases are tried before those of
+    /// `other`, and categories, context rules, custom actions and modes of
+    /// `other` are only added when this configuration does not define them.
+    /// The metadata of this configuration is kept.
     ///
     /// # Arguments
     ///
@@ -165,15 +462,37 @@ impl TokenizerConfig {
         for (category, patterns) in other.patterns {
             self.patterns
                 .entry(category)
-                .or_insert_with(Vec::new)
+                .or_default()
                 .extend(patterns);
         }
 
         // Merge context rules
-        self.context_rules.extend(other.context_rules);
+        for (name, rule) in other.context_rules {
+            self.context_rules.entry(name).or_insert(rule);
+        }
 
         // Merge custom actions
-        self.custom_actions.extend(other.custom_actions);
+        for (name, action) in other.custom_actions {
+            self.custom_actions.entry(name).or_insert(action);
+        }
+
+        // Merge special rules
+        self.special_rules.extend(other.special_rules);
+
+        // Merge comment rules
+        self.comments.extend(other.comments);
+
+        // Merge contextual rules
+        self.contextual_rules.extend(other.contextual_rules);
+
+        // Merge lexer modes
+        for (name, mode) in other.modes {
+            self.modes.entry(name).or_insert(mode);
+        }
+
+        // Merge phrases, keeping the longest ones first
+        self.phrases.extend(other.phrases);
+        self.phrases.sort_by_key(|phrase| std::cmp::Reverse(phrase.words.len()));
 
         Ok(())
     }
@@ -181,6 +500,8 @@ impl TokenizerConfig {
     /// Creates a TokenizerConfig from a RawTokenizerConfig.
     ///
     /// This method compiles the raw configuration, including compiling regex patterns.
+    /// Mode transitions are not validated, since the modes may be defined by an
+    /// imported configuration; see `validate_transitions`.
     ///
     /// # Arguments
     ///
@@ -190,49 +511,268 @@ impl TokenizerConfig {
     ///
     /// * `Result<Self, ConfigError>` - The compiled TokenizerConfig or an error
     pub fn from_raw(raw_config: RawTokenizerConfig) -> Result<Self, ConfigError> {
-        let mut patterns = HashMap::new();
+        let case_sensitive = raw_config.metadata.case_sensitive;
+        let patterns = Self::compile_patterns(raw_config.patterns, case_sensitive)?;
 
-        for (category, raw_patterns) in raw_config.patterns {
-            let mut compiled_patterns = Vec::new();
-            for raw_pattern in raw_patterns {
-                let compiled_regex = Regex::new(&raw_pattern.regex)
-                    .map_err(|_| ConfigError::InvalidRegex(raw_pattern.regex.clone()))?;
-                compiled_patterns.push(CompiledPatternConfig {
-                    regex: compiled_regex,
-                    subcategory: raw_pattern.subcategory,
-                });
+        for rule in &raw_config.comments {
+            rule.validate()?;
+        }
+        for rule in &raw_config.special_rules {
+            Self::validate_after(rule.after.as_ref())?;
+        }
+
+        let mut modes = HashMap::new();
+        for (name, raw_mode) in raw_config.modes {
+            let end = raw_mode
+                .end
+                .as_ref()
+                .map(|end| Self::compile_regex(end, case_sensitive))
+                .transpose()?;
+            let end_at_start = raw_mode
+                .end
+                .as_ref()
+                .map(|end| Self::compile_regex(&format!("^(?:{})", end), case_sensitive))
+                .transpose()?;
+            for rule in &raw_mode.comments {
+                rule.validate()?;
             }
-            patterns.insert(category, compiled_patterns);
+            for rule in &raw_mode.special_rules {
+                Self::validate_after(rule.after.as_ref())?;
+            }
+            let mode = ModeConfig {
+                token_categories: raw_mode.token_categories,
+                patterns: Self::compile_patterns(raw_mode.patterns, case_sensitive)?,
+                special_rules: raw_mode.special_rules,
+                comments: raw_mode.comments,
+                config: raw_mode.config,
+                end,
+                end_at_start,
+                region: raw_mode.region,
+            };
+            modes.insert(name, mode);
+        }
+
+        let mut phrases = Vec::new();
+        for raw_phrase in raw_config.phrases {
+            if raw_phrase.words.is_empty() {
+                return Err(ConfigError::MissingField("phrases.words".to_string()));
+            }
+            let emit = match raw_phrase.emit.as_deref() {
+                None | Some("composite") => PhraseEmit::Composite,
+                Some("members") => PhraseEmit::Members,
+                Some(other) => {
+                    return Err(ConfigError::ConfigurationError(format!(
+                        "Invalid phrase emit mode: {}",
+                        other
+                    )))
+                }
+            };
+            phrases.push(Phrase {
+                words: raw_phrase
+                    .words
+                    .iter()
+                    .map(|w| if case_sensitive { w.clone() } else { w.to_uppercase() })
+                    .collect(),
+                category: raw_phrase.category.unwrap_or_else(|| "Keyword".to_string()),
+                subcategory: raw_phrase.subcategory,
+                emit,
+            });
         }
-        
-        let special_rules = raw_config.special_rules
-            .into_iter()
-            .map(|rule| SpecialRule {
-                start: rule.start,
-                end: rule.end,
-                start_column: rule.start_column,
-                min_length: rule.min_length,
-                regex: rule.regex,
-                token_type: rule.token_type,
-            })
-            .collect();
+        phrases.sort_by_key(|phrase| std::cmp::Reverse(phrase.words.len()));
 
         // Validar campos requeridos
         if raw_config.metadata.language_version.is_empty() {
             return Err(ConfigError::MissingField("language_version".to_string()));
         }
+        if raw_config.metadata.tab_width == 0 {
+            return Err(ConfigError::ConfigurationError(
+                "tab_width must be at least 1".to_string(),
+            ));
+        }
 
-        Ok(TokenizerConfig {
+        let config = TokenizerConfig {
             metadata: raw_config.metadata,
             token_categories: raw_config.token_categories,
             patterns,
             context_rules: raw_config.context_rules,
             custom_actions: raw_config.custom_actions,
             imports: raw_config.imports,
-            special_rules,
+            special_rules: raw_config.special_rules,
+            comments: raw_config.comments,
+            contextual_rules: raw_config.contextual_rules,
+            phrases,
+            modes,
+        };
+        Ok(config)
+    }
+
+    /// Compiles the raw patterns of a mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw_patterns` - The raw patterns for each token category
+    /// * `case_sensitive` - Whether the patterns are matched case-sensitively,
+    ///   unless a pattern sets `case_sensitive` itself
+    ///
+    /// # Returns
+    ///
+    /// * `Result<HashMap<String, Vec<CompiledPatternConfig>>, ConfigError>` - The compiled patterns
+    ///   or an error if a regex is invalid
+    fn compile_patterns(
+        raw_patterns: HashMap<String, Vec<RawPatternConfig>>,
+        case_sensitive: bool,
+    ) -> Result<HashMap<String, Vec<CompiledPatternConfig>>, ConfigError> {
+        let mut patterns = HashMap::new();
+        for (category, raw_patterns) in raw_patterns {
+            let mut compiled_patterns = Vec::new();
+            for raw_pattern in raw_patterns {
+                let case_sensitive = raw_pattern.case_sensitive.unwrap_or(case_sensitive);
+                let compiled_regex = Self::compile_regex(&raw_pattern.regex, case_sensitive)?;
+                let followed_by = raw_pattern
+                    .followed_by
+                    .map(|regex| Self::compile_regex(&format!("^(?:{})", regex), case_sensitive))
+                    .transpose()?;
+                Self::validate_after(raw_pattern.after.as_ref())?;
+                compiled_patterns.push(CompiledPatternConfig {
+                    regex: compiled_regex,
+                    subcategory: raw_pattern.subcategory,
+                    followed_by,
+                    attached: raw_pattern.attached.unwrap_or(false),
+                    after: raw_pattern.after,
+                    push: raw_pattern.push,
+                    push_after: raw_pattern.push_after,
+                    pop: raw_pattern.pop.unwrap_or(false),
+                });
+            }
+            patterns.insert(category, compiled_patterns);
+        }
+        Ok(patterns)
+    }
+
+    /// Compiles a regex from the configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `regex` - The regular expression string
+    /// * `case_sensitive` - Whether the regex should match case-sensitively
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Regex, ConfigError>` - The compiled regex, or an InvalidRegex error
+    fn compile_regex(regex: &str, case_sensitive: bool) -> Result<Regex, ConfigError> {
+        RegexBuilder::new(regex)
+            .case_insensitive(!case_sensitive)
+            .build()
+            .map_err(|_| ConfigError::InvalidRegex(regex.to_string()))
+    }
+
+    /// Checks that no `after` entry of a pattern or special rule is empty,
+    /// since an empty sequence would precede every token.
+    ///
+    /// # Arguments
+    ///
+    /// * `after` - The `after` option of the rule, if set
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), ConfigError>` - Ok if all entries name at least one token, Err otherwise
+    fn validate_after(after: Option<&Vec<String>>) -> Result<(), ConfigError> {
+        if after.is_some_and(|entries| entries.iter().any(|entry| entry.trim().is_empty())) {
+            return Err(ConfigError::ConfigurationError(
+                "Empty entry in after condition".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the number of preceding words the `after` conditions of the
+    /// patterns and special rules of all modes can look at.
+    pub fn lookbehind_length(&self) -> usize {
+        let pattern_entries = std::iter::once(&self.patterns)
+            .chain(self.modes.values().map(|mode| &mode.patterns))
+            .flat_map(|patterns| patterns.values().flatten())
+            .filter_map(|pattern| pattern.after.as_ref());
+        let rule_entries = std::iter::once(&self.special_rules)
+            .chain(self.modes.values().map(|mode| &mode.special_rules))
+            .flatten()
+            .filter_map(|rule| rule.after.as_ref());
+        pattern_entries
+            .chain(rule_entries)
+            .flatten()
+            .map(|entry| entry.split_whitespace().count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Checks an `after` condition against the words of the preceding tokens.
+    ///
+    /// Each entry of `after` is a whitespace separated list of token values,
+    /// such as `"TYPE REF TO"`, that must match the words of the tokens right
+    /// before the candidate. Keyword phrases contribute each of their words,
+    /// so `"TYPE REF TO"` also matches the phrase token `TYPE REF TO`. Words
+    /// are compared case-insensitively unless `metadata.case_sensitive` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `after` - The entries of the condition
+    /// * `previous` - The words of the preceding significant tokens, nearest last
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - True if one of the entries matches the end of `previous`
+    pub fn is_preceded_by(&self, after: &[String], previous: &[String]) -> bool {
+        after.iter().any(|entry| {
+            let words: Vec<&str> = entry.split_whitespace().collect();
+            words.len() <= previous.len()
+                && words.iter().rev().zip(previous.iter().rev()).all(|(word, prev)| {
+                    if self.metadata.case_sensitive {
+                        word == prev
+                    } else {
+                        word.eq_ignore_ascii_case(prev)
+                    }
+                })
         })
     }
 
+    /// Checks that every `push` transition names a defined lexer mode.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), ConfigError>` - Ok if all transitions are valid, Err otherwise
+    pub fn validate_transitions(&self) -> Result<(), ConfigError> {
+        let pattern_targets = std::iter::once(&self.patterns)
+            .chain(self.modes.values().map(|mode| &mode.patterns))
+            .flat_map(|patterns| patterns.values().flatten())
+            .filter_map(|pattern| pattern.push.as_ref());
+        let rule_targets = std::iter::once(&self.special_rules)
+            .chain(self.modes.values().map(|mode| &mode.special_rules))
+            .flatten()
+            .filter_map(|rule| rule.push.as_ref());
+
+        for target in pattern_targets.chain(rule_targets) {
+            if target != MAIN_MODE && !self.modes.contains_key(target) {
+                return Err(ConfigError::ConfigurationError(format!(
+                    "Unknown lexer mode: {}",
+                    target
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Retrieves a lexer mode by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the mode
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&ModeConfig>` - The mode if it exists
+    pub fn get_mode(&self, name: &str) -> Option<&ModeConfig> {
+        self.modes.get(name)
+    }
+
     /// Retrieves the compiled patterns for a given category.
     ///
     /// # Arguments
@@ -255,7 +795,7 @@ impl TokenizerConfig {
     pub fn add_pattern(&mut self, category: String, pattern: CompiledPatternConfig) {
         self.patterns
             .entry(category)
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(pattern);
     }
 
@@ -5,9 +5,34 @@
 //! for the ABAP Tokenizer. It handles the initial loading of the raw configuration
 //! and its conversion into a usable TokenizerConfig structure.
 use crate::error::ConfigError;
+use crate::config::dialect::{embedded_config, Dialect};
 use crate::config::tokenizer_config::{RawTokenizerConfig, TokenizerConfig};
 use std::fs;
+use std::path::{Component, Path, PathBuf};
 
+/// Where configuration files are read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ConfigSource {
+    /// The file system.
+    FileSystem,
+    /// The configurations embedded in the binary, see `dialect`.
+    Embedded,
+}
+
+impl ConfigSource {
+    /// Reads a configuration file.
+    fn read(&self, path: &Path) -> Result<String, ConfigError> {
+        match self {
+            ConfigSource::FileSystem => fs::read_to_string(path)
+                .map_err(|e| ConfigError::IoError(format!("Failed to read config file: {}", e))),
+            ConfigSource::Embedded => embedded_config(path)
+                .map(str::to_string)
+                .ok_or_else(|| {
+                    ConfigError::IoError(format!("No built-in config file: {}", path.display()))
+                }),
+        }
+    }
+}
 
 /// Loads and parses a TOML configuration file for the ABAP Tokenizer.
 ///
@@ -15,6 +40,12 @@ use std::fs;
 /// 1. Reads the TOML file from the given path.
 /// 2. Parses the TOML content into a RawTokenizerConfig structure.
 /// 3. Converts the RawTokenizerConfig into a fully initialized TokenizerConfig.
+/// 4. Loads the configuration files referenced by lexer modes, relative to `path`.
+/// 5. Merges the configuration files listed in `imports`, relative to `path`,
+///    and then the built-in profile named by `profile`, if any (see `Dialect`).
+///    The definitions of the file itself take precedence over both (see
+///    `TokenizerConfig::merge`).
+/// 6. Checks that every mode transition names a defined mode.
 ///
 /// # Arguments
 ///
@@ -23,7 +54,7 @@ use std::fs;
 /// # Returns
 ///
 /// * `Result<TokenizerConfig, ConfigError>` - The parsed and initialized TokenizerConfig on success,
-///    or a ConfigError if any step of the loading process fails.
+///   or a ConfigError if any step of the loading process fails.
 ///
 /// # Errors
 ///
@@ -31,14 +62,81 @@ use std::fs;
 /// * The file cannot be read (e.g., file not found, permissions issues)
 /// * The TOML content is invalid or cannot be parsed
 /// * The conversion from RawTokenizerConfig to TokenizerConfig fails (e.g., invalid regex patterns)
-
+/// * The imports or the configs of lexer modes refer back to a file being loaded
+/// * The `profile` does not name a built-in profile
 pub fn load_toml_config(path: &str) -> Result<TokenizerConfig, ConfigError> {
+    load_config(Path::new(path), ConfigSource::FileSystem)
+}
+
+/// Loads a configuration file and everything it imports. See `load_toml_config`.
+pub(crate) fn load_config(path: &Path, source: ConfigSource) -> Result<TokenizerConfig, ConfigError> {
+    let config = load_imported(path, source, &mut Vec::new())?;
+    config.validate_transitions()?;
+    Ok(config)
+}
+
+/// Loads a configuration file, tracking the chain of imports and mode
+/// configs to detect cycles.
+fn load_imported(
+    path: &Path,
+    source: ConfigSource,
+    chain: &mut Vec<PathBuf>,
+) -> Result<TokenizerConfig, ConfigError> {
+    let path = normalize(path);
+    if chain.contains(&path) {
+        return Err(ConfigError::ConfigurationError(format!(
+            "Recursive import of {}",
+            path.display()
+        )));
+    }
     // Read the content of the TOML file
-    let content = fs::read_to_string(path)
-        .map_err(|e| ConfigError::IoError(format!("Failed to read config file: {}", e)))?;
+    let content = source.read(&path)?;
     // Parse the TOML content into a RawTokenizerConfig
     let raw_config: RawTokenizerConfig = toml::from_str(&content)
         .map_err(|e| ConfigError::ParseError(format!("Failed to parse TOML: {}", e)))?;
+    let imports = raw_config.imports.clone();
+    let profile = raw_config.profile.clone();
     // Convert the RawTokenizerConfig into a TokenizerConfig
-    TokenizerConfig::from_raw(raw_config)
-}
\ No newline at end of file
+    let mut config = TokenizerConfig::from_raw(raw_config)?;
+    // Load the rules of modes defined in separate files. They share the
+    // chain of imports, so that a mode config referring back to a file being
+    // loaded is reported instead of recursing forever.
+    let base_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    chain.push(path.clone());
+    for mode in config.modes.values_mut() {
+        if let Some(mode_path) = &mode.config {
+            let mode_config = load_imported(&base_dir.join(mode_path), source, chain)?;
+            mode_config.validate_transitions()?;
+            mode.use_rules_of(mode_config);
+        }
+    }
+    // Merge the imported configurations
+    for import in imports.unwrap_or_default() {
+        let imported = load_imported(&base_dir.join(import), source, chain)?;
+        config.merge(imported)?;
+    }
+    chain.pop();
+    if let Some(profile) = profile {
+        let dialect: Dialect = profile.parse().map_err(|_| {
+            ConfigError::ConfigurationError(format!("No built-in profile {}", profile))
+        })?;
+        config.merge(dialect.config()?)?;
+    }
+    Ok(config)
+}
+
+/// Resolves the `.` and `..` components of a path without accessing the file
+/// system, so that embedded files are found by a single name.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
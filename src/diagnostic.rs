@@ -0,0 +1,91 @@
+// abap-tokenizer/src/diagnostic.rs
+//! Diagnostics reported while processing ABAP sources.
+//!
+//! Unlike the errors in `error`, diagnostics do not abort processing. They are
+//! collected alongside the result (for example a syntax tree) so callers can
+//! report every problem in a file instead of stopping at the first one.
+use std::fmt;
+
+/// Severity of a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// A problem that makes the result incomplete or unreliable.
+    Error,
+    /// A suspicious construct that was processed anyway.
+    Warning,
+}
+
+/// A non-fatal problem found in the source, with its position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// How serious the problem is.
+    pub severity: Severity,
+    /// Human readable description of the problem.
+    pub message: String,
+    /// The line number where the problem was found.
+    pub line: usize,
+    /// The column number where the problem was found.
+    pub column: usize,
+    /// Identifier of the source file, 0 for the main file.
+    pub file_id: usize,
+}
+
+impl Diagnostic {
+    /// Creates a new error diagnostic.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Description of the problem
+    /// * `line` - The line number where the problem was found
+    /// * `column` - The column number where the problem was found
+    pub fn error(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            message: message.into(),
+            line,
+            column,
+            file_id: 0,
+        }
+    }
+
+    /// Creates a new warning diagnostic.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - Description of the problem
+    /// * `line` - The line number where the problem was found
+    /// * `column` - The column number where the problem was found
+    pub fn warning(message: impl Into<String>, line: usize, column: usize) -> Self {
+        Diagnostic {
+            severity: Severity::Warning,
+            message: message.into(),
+            line,
+            column,
+            file_id: 0,
+        }
+    }
+
+    /// Sets the source file the diagnostic refers to.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - Identifier of the source file
+    pub fn in_file(mut self, file_id: usize) -> Self {
+        self.file_id = file_id;
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.line, self.column, severity, self.message
+        )
+    }
+}
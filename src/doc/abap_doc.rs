@@ -0,0 +1,138 @@
+// abap-tokenizer/src/doc/abap_doc.rs
+//! Parsing of ABAP Doc comment blocks.
+//!
+//! An ABAP Doc block is a sequence of `"!` comment lines. It starts with a
+//! free-text description, which may contain a short text in
+//! `<p class="shorttext">...</p>`, followed by tags documenting parameters
+//! and exceptions:
+//!
+//! ```text
+//! "! <p class="shorttext synchronized">Calculates the total</p>
+//! "! Sums all items of the order.
+//! "! @parameter iv_order | Order number
+//! "! @raising zcx_not_found | The order does not exist
+//! ```
+
+/// Prefix of an ABAP Doc comment line.
+pub const DOC_PREFIX: &str = "\"!";
+
+/// Documentation of a parameter or exception.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocTag {
+    /// The name of the parameter or exception.
+    pub name: String,
+    /// The description following the `|` separator.
+    pub text: String,
+}
+
+/// A parsed ABAP Doc comment block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocBlock {
+    /// The short text from `<p class="shorttext">`, if any.
+    pub shorttext: Option<String>,
+    /// The description, without the short text and the tags.
+    pub description: String,
+    /// The `@parameter` tags, in source order.
+    pub parameters: Vec<DocTag>,
+    /// The `@raising` and `@exception` tags, in source order.
+    pub raising: Vec<DocTag>,
+}
+
+impl DocBlock {
+    /// Parses the lines of an ABAP Doc block.
+    ///
+    /// Lines following a tag continue its description until the next tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `lines` - The comment lines, with or without their `"!` prefix
+    ///
+    /// # Returns
+    ///
+    /// The parsed DocBlock.
+    pub fn parse<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut block = DocBlock::default();
+        let mut description: Vec<&str> = Vec::new();
+        // Whether continuation lines belong to the last parameter or exception
+        let mut in_tag: Option<bool> = None;
+
+        for line in lines {
+            let line = line.trim_end();
+            let line = line.strip_prefix(DOC_PREFIX).unwrap_or(line).trim();
+            let tag = line
+                .split_once(char::is_whitespace)
+                .map_or((line, ""), |(tag, rest)| (tag, rest.trim()));
+            match tag {
+                ("@parameter", rest) => {
+                    block.parameters.push(Self::parse_tag(rest));
+                    in_tag = Some(true);
+                }
+                ("@raising" | "@exception", rest) => {
+                    block.raising.push(Self::parse_tag(rest));
+                    in_tag = Some(false);
+                }
+                _ => {
+                    let last = match in_tag {
+                        Some(true) => block.parameters.last_mut(),
+                        Some(false) => block.raising.last_mut(),
+                        None => None,
+                    };
+                    match last {
+                        Some(tag) if !line.is_empty() => {
+                            if !tag.text.is_empty() {
+                                tag.text.push(' ');
+                            }
+                            tag.text.push_str(line);
+                        }
+                        Some(_) => {}
+                        None => description.push(line),
+                    }
+                }
+            }
+        }
+
+        let description = description.join("\n");
+        let (shorttext, description) = Self::extract_shorttext(&description);
+        block.shorttext = shorttext;
+        block.description = description.trim().to_string();
+        block
+    }
+
+    /// Returns the short text if present, otherwise the first description line.
+    pub fn summary(&self) -> &str {
+        match &self.shorttext {
+            Some(shorttext) => shorttext,
+            None => self.description.lines().next().unwrap_or(""),
+        }
+    }
+
+    /// Parses `name | text` following a tag.
+    fn parse_tag(rest: &str) -> DocTag {
+        let (name, text) = rest.split_once('|').unwrap_or((rest, ""));
+        DocTag {
+            name: name.trim().to_string(),
+            text: text.trim().to_string(),
+        }
+    }
+
+    /// Splits the `<p class="shorttext ...">` element from a description.
+    fn extract_shorttext(description: &str) -> (Option<String>, String) {
+        let Some(start) = description.find("<p class=\"shorttext") else {
+            return (None, description.to_string());
+        };
+        let Some(content_start) = description[start..].find('>').map(|i| start + i + 1) else {
+            return (None, description.to_string());
+        };
+        let Some(content_end) = description[content_start..].find("</p>").map(|i| content_start + i)
+        else {
+            return (None, description.to_string());
+        };
+        let shorttext = description[content_start..content_end].trim().to_string();
+        let rest = format!(
+            "{}{}",
+            &description[..start],
+            &description[content_end + "</p>".len()..]
+        );
+        (Some(shorttext), rest)
+    }
+}
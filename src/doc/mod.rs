@@ -0,0 +1,316 @@
+// abap-tokenizer/src/doc/mod.rs
+//! ABAP Doc extraction and API documentation generation.
+//!
+//! ABAP Doc comments (`"!`) are kept by the parser as `DocComment` trivia in
+//! front of the token they document. This module collects them into
+//! `DocItem` values for the class, interface, method, attribute, type or
+//! constant declared there, and renders the items as Markdown or HTML.
+
+/// Parsing of ABAP Doc comment blocks.
+pub mod abap_doc;
+
+/// Markdown and HTML rendering of documented items.
+pub mod render;
+
+pub use abap_doc::{DocBlock, DocTag};
+pub use render::{render_html, render_markdown, DocFormat};
+
+use crate::config::TokenizerConfig;
+use crate::encoding::read_source;
+use crate::error::TokenizerError;
+use crate::parser::{self, SyntaxKind, SyntaxNode, SyntaxToken, SyntaxTree, TriviaKind};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extension of the ABAP source files documented by `docs_in_tree`.
+const SOURCE_EXTENSION: &str = "abap";
+
+/// The kind of a documented declaration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocItemKind {
+    /// `CLASS ... DEFINITION`.
+    Class,
+    /// `INTERFACE`.
+    Interface,
+    /// `METHODS` or `CLASS-METHODS`.
+    Method,
+    /// `EVENTS` or `CLASS-EVENTS`.
+    Event,
+    /// `DATA` or `CLASS-DATA`.
+    Attribute,
+    /// `TYPES`.
+    Type,
+    /// `CONSTANTS`.
+    Constant,
+    /// Any other statement.
+    Other,
+}
+
+/// A declaration with its ABAP Doc block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocItem {
+    /// The kind of the declaration.
+    pub kind: DocItemKind,
+    /// The declared name.
+    pub name: String,
+    /// The class or interface the declaration belongs to, if any.
+    pub container: Option<String>,
+    /// The documentation.
+    pub doc: DocBlock,
+    /// The line number of the declaration.
+    pub line: usize,
+}
+
+/// The documented items of a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDocs {
+    /// The path of the file.
+    pub file: String,
+    /// The documented items, in source order.
+    pub items: Vec<DocItem>,
+}
+
+impl DocItemKind {
+    /// Determines the kind of a declaration from its leading keyword.
+    fn from_keyword(keyword: &str) -> Self {
+        match keyword {
+            "CLASS" => DocItemKind::Class,
+            "INTERFACE" => DocItemKind::Interface,
+            "METHODS" | "CLASS-METHODS" => DocItemKind::Method,
+            "EVENTS" | "CLASS-EVENTS" => DocItemKind::Event,
+            "DATA" | "CLASS-DATA" => DocItemKind::Attribute,
+            "TYPES" => DocItemKind::Type,
+            "CONSTANTS" => DocItemKind::Constant,
+            _ => DocItemKind::Other,
+        }
+    }
+
+    /// Returns the name of the kind as used in generated documentation.
+    pub fn label(&self) -> &'static str {
+        match self {
+            DocItemKind::Class => "Class",
+            DocItemKind::Interface => "Interface",
+            DocItemKind::Method => "Method",
+            DocItemKind::Event => "Event",
+            DocItemKind::Attribute => "Attribute",
+            DocItemKind::Type => "Type",
+            DocItemKind::Constant => "Constant",
+            DocItemKind::Other => "Declaration",
+        }
+    }
+}
+
+/// Collects the documented declarations of a syntax tree.
+///
+/// A doc block in front of a statement documents the name the statement
+/// declares. A doc block in front of a name inside a chained statement, as in
+/// `DATA: "! Count \n mv_count TYPE i.`, documents that name. A doc block in
+/// front of a parameter of `METHODS` or `EVENTS` becomes a `@parameter` tag
+/// of the method or event.
+///
+/// # Arguments
+///
+/// * `tree` - The syntax tree of a source file
+///
+/// # Returns
+///
+/// The documented items, in source order.
+pub fn extract_docs(tree: &SyntaxTree) -> Vec<DocItem> {
+    let mut items = Vec::new();
+    collect_items(&tree.root, None, &mut items);
+    items
+}
+
+/// Parses a source file and collects its documented declarations.
+///
+/// # Arguments
+///
+/// * `path` - The path of the ABAP source file
+/// * `config` - The TokenizerConfig to use for tokenization
+///
+/// # Returns
+///
+/// * `Result<FileDocs, TokenizerError>` - The documented items of the file,
+///   or an error if the file cannot be read or tokenized.
+pub fn docs_in_file(path: &Path, config: TokenizerConfig) -> Result<FileDocs, TokenizerError> {
+    let source = read_source(path, config.metadata.encoding)?;
+    let tree = parser::parse(&source.text, config)?;
+    Ok(FileDocs {
+        file: path.display().to_string(),
+        items: extract_docs(&tree),
+    })
+}
+
+/// Collects the documented declarations of every `.abap` file below a directory.
+///
+/// Files are visited in path order, so the output is stable.
+///
+/// # Arguments
+///
+/// * `root` - The directory to search, or a single source file
+/// * `config` - The TokenizerConfig to use for tokenization
+///
+/// # Returns
+///
+/// * `Result<Vec<FileDocs>, TokenizerError>` - The documented items of each
+///   file, or the first error encountered.
+pub fn docs_in_tree(root: &Path, config: &TokenizerConfig) -> Result<Vec<FileDocs>, TokenizerError> {
+    let mut files = Vec::new();
+    collect_source_files(root, &mut files)?;
+    files.sort();
+    files
+        .iter()
+        .map(|file| docs_in_file(file, config.clone()))
+        .collect()
+}
+
+/// Recursively collects the ABAP source files below `path`.
+fn collect_source_files(path: &Path, files: &mut Vec<PathBuf>) -> Result<(), TokenizerError> {
+    if path.is_file() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+    let io_error = |e: std::io::Error| TokenizerError::IoError(format!("{}: {}", path.display(), e));
+    for entry in fs::read_dir(path).map_err(io_error)? {
+        let entry_path = entry.map_err(io_error)?.path();
+        if entry_path.is_dir() {
+            collect_source_files(&entry_path, files)?;
+        } else if entry_path.extension().is_some_and(|ext| ext == SOURCE_EXTENSION) {
+            files.push(entry_path);
+        }
+    }
+    Ok(())
+}
+
+/// Walks a node, tracking the enclosing class or interface.
+fn collect_items(node: &SyntaxNode, container: Option<&str>, items: &mut Vec<DocItem>) {
+    let mut container = container.map(String::from);
+    for child in node.child_nodes() {
+        if child.child_nodes().next().is_some() {
+            collect_items(child, container.as_deref(), items);
+            continue;
+        }
+        let tokens = child.tokens();
+        let keyword = tokens.first().map(|token| token.upper()).unwrap_or_default();
+        if matches!(
+            node.kind,
+            SyntaxKind::ClassDefinition | SyntaxKind::InterfaceDefinition
+        ) && container.is_none()
+        {
+            container = declared_name(&tokens, 0);
+        }
+        let kind = DocItemKind::from_keyword(&keyword);
+        // Index of the item documenting the current element of a chain
+        let first_item = items.len();
+        for (index, token) in tokens.iter().enumerate() {
+            let Some(doc) = doc_block(token) else {
+                continue;
+            };
+            if index > 0 && has_parameters(kind) && !starts_chain_element(&tokens, index) {
+                add_parameter_doc(&tokens, index, doc, kind, container.as_deref(), items, first_item);
+                continue;
+            }
+            let name = if index == 0 {
+                declared_name(&tokens, 0)
+            } else {
+                Some(token.token.value.clone())
+            };
+            let own_container = matches!(kind, DocItemKind::Class | DocItemKind::Interface);
+            items.push(DocItem {
+                kind,
+                name: name.unwrap_or_default(),
+                container: if own_container { None } else { container.clone() },
+                doc,
+                line: token.token.line,
+            });
+        }
+    }
+}
+
+/// Returns true if doc blocks inside a declaration of this kind can document
+/// its parameters.
+fn has_parameters(kind: DocItemKind) -> bool {
+    matches!(kind, DocItemKind::Method | DocItemKind::Event)
+}
+
+/// Returns true if the token at `index` is the name declared by an element
+/// of a chained statement, as `m2` in `METHODS: m1, m2.`.
+fn starts_chain_element(tokens: &[&SyntaxToken], index: usize) -> bool {
+    index == 1 || matches!(tokens[index - 1].symbol(), ":" | ",")
+}
+
+/// Attaches the doc block in front of a parameter to its method or event.
+///
+/// The doc becomes a `@parameter` tag of the item documenting the chain
+/// element the parameter belongs to, as if it were written in front of the
+/// method. An item without documentation of its own is created if needed.
+fn add_parameter_doc(
+    tokens: &[&SyntaxToken],
+    index: usize,
+    doc: DocBlock,
+    kind: DocItemKind,
+    container: Option<&str>,
+    items: &mut Vec<DocItem>,
+    first_item: usize,
+) {
+    let element = tokens[..index]
+        .iter()
+        .rposition(|token| matches!(token.symbol(), ":" | ","))
+        .unwrap_or(0);
+    let Some(declared) = tokens
+        .iter()
+        .skip(element + 1)
+        .find(|token| !matches!(token.symbol(), ":" | ","))
+    else {
+        return;
+    };
+    let name = &declared.token.value;
+    let position = items[first_item..]
+        .iter()
+        .position(|item| item.kind == kind && &item.name == name)
+        .map(|position| first_item + position);
+    let position = position.unwrap_or_else(|| {
+        items.push(DocItem {
+            kind,
+            name: name.clone(),
+            container: container.map(String::from),
+            doc: DocBlock::default(),
+            line: declared.token.line,
+        });
+        items.len() - 1
+    });
+    let text = [doc.shorttext.as_deref().unwrap_or(""), &doc.description.replace('\n', " ")]
+        .iter()
+        .filter(|part| !part.is_empty())
+        .copied()
+        .collect::<Vec<_>>()
+        .join(" ");
+    items[position].doc.parameters.push(DocTag {
+        name: tokens[index].token.value.clone(),
+        text,
+    });
+}
+
+/// Returns the name declared by the statement whose keyword is at `index`.
+fn declared_name(tokens: &[&SyntaxToken], index: usize) -> Option<String> {
+    tokens
+        .iter()
+        .skip(index + 1)
+        .find(|token| !matches!(token.symbol(), ":" | ","))
+        .map(|token| token.token.value.clone())
+}
+
+/// Parses the ABAP Doc comments in front of a token, if any.
+fn doc_block(token: &SyntaxToken) -> Option<DocBlock> {
+    let lines: Vec<&str> = token
+        .leading_trivia
+        .iter()
+        .filter(|trivia| trivia.kind == TriviaKind::DocComment)
+        .map(|trivia| trivia.text.as_str())
+        .collect();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(DocBlock::parse(lines))
+    }
+}
@@ -0,0 +1,157 @@
+// abap-tokenizer/src/doc/render.rs
+//! Markdown and HTML rendering of documented items.
+use super::{DocItem, DocItemKind, DocTag, FileDocs};
+use std::fmt::Write;
+
+/// Output format of the generated documentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocFormat {
+    /// GitHub-flavored Markdown.
+    Markdown,
+    /// A standalone HTML page.
+    Html,
+}
+
+impl DocFormat {
+    /// Renders the documentation of several files in this format.
+    ///
+    /// # Arguments
+    ///
+    /// * `files` - The documented items of each file
+    pub fn render(&self, files: &[FileDocs]) -> String {
+        match self {
+            DocFormat::Markdown => render_markdown(files),
+            DocFormat::Html => render_html(files),
+        }
+    }
+}
+
+/// Returns the qualified name of an item, such as `zcl_order=>get_total`.
+fn qualified_name(item: &DocItem) -> String {
+    match &item.container {
+        Some(container) => format!("{}=>{}", container, item.name),
+        None => item.name.clone(),
+    }
+}
+
+/// Renders the documentation of several files as Markdown.
+///
+/// # Arguments
+///
+/// * `files` - The documented items of each file
+///
+/// # Returns
+///
+/// The Markdown document, with one section per file.
+pub fn render_markdown(files: &[FileDocs]) -> String {
+    let mut out = String::from("# API Documentation\n");
+    for file in files.iter().filter(|file| !file.items.is_empty()) {
+        let _ = write!(out, "\n## {}\n\n", file.file);
+        for item in &file.items {
+            let level = match item.kind {
+                DocItemKind::Class | DocItemKind::Interface => "###",
+                _ => "####",
+            };
+            let _ = write!(
+                out,
+                "{} {} `{}`\n\n",
+                level,
+                item.kind.label(),
+                qualified_name(item)
+            );
+            if let Some(shorttext) = &item.doc.shorttext {
+                let _ = writeln!(out, "*{}*\n", shorttext);
+            }
+            if !item.doc.description.is_empty() {
+                let _ = writeln!(out, "{}\n", item.doc.description);
+            }
+            write_markdown_tags(&mut out, "Parameter", &item.doc.parameters);
+            write_markdown_tags(&mut out, "Exception", &item.doc.raising);
+        }
+    }
+    out
+}
+
+/// Writes a Markdown table of parameters or exceptions.
+fn write_markdown_tags(out: &mut String, header: &str, tags: &[DocTag]) {
+    if tags.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "| {} | Description |\n| --- | --- |", header);
+    for tag in tags {
+        let _ = writeln!(out, "| `{}` | {} |", tag.name, tag.text.replace('|', "\\|"));
+    }
+    out.push('\n');
+}
+
+/// Renders the documentation of several files as a standalone HTML page.
+///
+/// # Arguments
+///
+/// * `files` - The documented items of each file
+///
+/// # Returns
+///
+/// The HTML document, with one section per file.
+pub fn render_html(files: &[FileDocs]) -> String {
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>API Documentation</title>\n</head>\n<body>\n<h1>API Documentation</h1>\n",
+    );
+    for file in files.iter().filter(|file| !file.items.is_empty()) {
+        let _ = writeln!(out, "<section>\n<h2>{}</h2>", escape_html(&file.file));
+        for item in &file.items {
+            let level = match item.kind {
+                DocItemKind::Class | DocItemKind::Interface => "h3",
+                _ => "h4",
+            };
+            let _ = writeln!(
+                out,
+                "<{level}>{} <code>{}</code></{level}>",
+                item.kind.label(),
+                escape_html(&qualified_name(item)),
+                level = level
+            );
+            if let Some(shorttext) = &item.doc.shorttext {
+                let _ = writeln!(out, "<p><em>{}</em></p>", escape_html(shorttext));
+            }
+            if !item.doc.description.is_empty() {
+                // ABAP Doc descriptions may contain HTML markup of their own
+                let _ = writeln!(out, "<p>{}</p>", item.doc.description);
+            }
+            write_html_tags(&mut out, "Parameter", &item.doc.parameters);
+            write_html_tags(&mut out, "Exception", &item.doc.raising);
+        }
+        out.push_str("</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+/// Writes an HTML table of parameters or exceptions.
+fn write_html_tags(out: &mut String, header: &str, tags: &[DocTag]) {
+    if tags.is_empty() {
+        return;
+    }
+    let _ = writeln!(
+        out,
+        "<table>\n<tr><th>{}</th><th>Description</th></tr>",
+        header
+    );
+    for tag in tags {
+        let _ = writeln!(
+            out,
+            "<tr><td><code>{}</code></td><td>{}</td></tr>",
+            escape_html(&tag.name),
+            escape_html(&tag.text)
+        );
+    }
+    out.push_str("</table>\n");
+}
+
+/// Escapes the characters with a special meaning in HTML.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,266 @@
+// abap-tokenizer/src/encoding.rs
+//! Character encodings of ABAP source files.
+//!
+//! Sources exported from SAP systems are not always UTF-8: older systems
+//! write ISO-8859-1 or Windows-1252, and some tools write UTF-16 with a byte
+//! order mark. Source files are decoded to UTF-8 before tokenizing; the
+//! resulting `SourceText` maps byte offsets in the decoded text back to the
+//! original file.
+//!
+//! Lines and columns are counted in characters, so they are the same in the
+//! decoded text and in the original file. Spans are byte offsets and have to
+//! be converted with `SourceText::original_span`.
+use crate::error::TokenizerError;
+use crate::tokenizer::token::Span;
+use serde::Deserialize;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Byte order mark of UTF-8 files.
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+/// Byte order mark of UTF-16 little-endian files.
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+/// Byte order mark of UTF-16 big-endian files.
+const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+/// Characters of Windows-1252 bytes 0x80 to 0x9F. Bytes not assigned by the
+/// code page map to the C1 control character of the same value, as in
+/// ISO-8859-1.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+/// A character encoding of source files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum Encoding {
+    /// UTF-8, the default.
+    Utf8,
+    /// ISO-8859-1 (Latin-1), SAP code page 1100.
+    Latin1,
+    /// Windows-1252, a superset of ISO-8859-1 with printable characters such
+    /// as `€` in the range 0x80 to 0x9F.
+    Windows1252,
+    /// UTF-16, little-endian.
+    Utf16Le,
+    /// UTF-16, big-endian.
+    Utf16Be,
+}
+
+impl Encoding {
+    /// Returns the canonical name of the encoding, as accepted by `from_str`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "utf-8",
+            Encoding::Latin1 => "iso-8859-1",
+            Encoding::Windows1252 => "windows-1252",
+            Encoding::Utf16Le => "utf-16le",
+            Encoding::Utf16Be => "utf-16be",
+        }
+    }
+
+    /// Detects the encoding of a byte order mark at the start of `bytes`.
+    ///
+    /// # Returns
+    ///
+    /// The encoding and the length of the byte order mark, or None if `bytes`
+    /// does not start with one.
+    fn detect_bom(bytes: &[u8]) -> Option<(Self, usize)> {
+        [
+            (Encoding::Utf8, UTF8_BOM),
+            (Encoding::Utf16Le, UTF16LE_BOM),
+            (Encoding::Utf16Be, UTF16BE_BOM),
+        ]
+        .into_iter()
+        .find(|(_, bom)| bytes.starts_with(bom))
+        .map(|(encoding, bom)| (encoding, bom.len()))
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    /// Parses an encoding name. Names are case-insensitive and accept the
+    /// usual aliases, such as `latin1` or `cp1252`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.to_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => Ok(Encoding::Utf8),
+            "iso-8859-1" | "latin1" | "latin-1" => Ok(Encoding::Latin1),
+            "windows-1252" | "cp1252" => Ok(Encoding::Windows1252),
+            "utf-16le" | "utf16le" => Ok(Encoding::Utf16Le),
+            "utf-16be" | "utf16be" => Ok(Encoding::Utf16Be),
+            _ => Err(format!("unknown encoding: {}", name)),
+        }
+    }
+}
+
+impl TryFrom<String> for Encoding {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        name.parse()
+    }
+}
+
+/// A source text decoded to UTF-8, with the mapping to the original bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceText {
+    /// The decoded text, without byte order mark.
+    pub text: String,
+    /// The encoding of the original file.
+    pub encoding: Encoding,
+    /// Whether the original file started with a byte order mark.
+    pub bom: bool,
+    /// Offsets in the decoded text and in the original bytes at which the
+    /// widths of the characters in both start to differ, in increasing order.
+    /// Between two checkpoints, characters have the same width in both.
+    checkpoints: Vec<(usize, usize)>,
+}
+
+impl SourceText {
+    /// Decodes the content of a source file.
+    ///
+    /// A byte order mark takes precedence over the given encoding. Without
+    /// either, the content is expected to be UTF-8.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The content of the file
+    /// * `encoding` - The encoding of the file, if known
+    ///
+    /// # Returns
+    ///
+    /// * `Result<SourceText, TokenizerError>` - The decoded text, or an error
+    ///   if the content is not valid in the encoding.
+    pub fn decode(bytes: &[u8], encoding: Option<Encoding>) -> Result<Self, TokenizerError> {
+        let (encoding, bom_length) = match Encoding::detect_bom(bytes) {
+            Some((encoding, length)) => (encoding, length),
+            None => (encoding.unwrap_or(Encoding::Utf8), 0),
+        };
+        let content = &bytes[bom_length..];
+        let mut source = SourceText {
+            text: String::with_capacity(content.len()),
+            encoding,
+            bom: bom_length > 0,
+            checkpoints: vec![(0, bom_length)],
+        };
+        match encoding {
+            Encoding::Utf8 => {
+                let text = std::str::from_utf8(content).map_err(|e| {
+                    TokenizerError::EncodingError(format!(
+                        "invalid UTF-8 at byte {}; specify the encoding of the file",
+                        bom_length + e.valid_up_to()
+                    ))
+                })?;
+                source.text.push_str(text);
+            }
+            Encoding::Latin1 => {
+                for &byte in content {
+                    source.push(char::from(byte), 1);
+                }
+            }
+            Encoding::Windows1252 => {
+                for &byte in content {
+                    let character = match byte {
+                        0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+                        _ => char::from(byte),
+                    };
+                    source.push(character, 1);
+                }
+            }
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                if content.len() % 2 != 0 {
+                    return Err(TokenizerError::EncodingError(
+                        "odd number of bytes in UTF-16 content".to_string(),
+                    ));
+                }
+                let units = content.chunks_exact(2).map(|pair| {
+                    let pair = [pair[0], pair[1]];
+                    match encoding {
+                        Encoding::Utf16Le => u16::from_le_bytes(pair),
+                        _ => u16::from_be_bytes(pair),
+                    }
+                });
+                for character in char::decode_utf16(units) {
+                    let character = character.map_err(|e| {
+                        TokenizerError::EncodingError(format!(
+                            "unpaired UTF-16 surrogate {:#06x}",
+                            e.unpaired_surrogate()
+                        ))
+                    })?;
+                    source.push(character, 2 * character.len_utf16());
+                }
+            }
+        }
+        Ok(source)
+    }
+
+    /// Converts a byte offset in the decoded text to an offset in the
+    /// original file.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - A byte offset in `text`, at a character boundary
+    pub fn original_offset(&self, offset: usize) -> usize {
+        let index = self
+            .checkpoints
+            .partition_point(|&(decoded, _)| decoded <= offset)
+            .saturating_sub(1);
+        let (decoded, original) = self.checkpoints[index];
+        original + (offset - decoded)
+    }
+
+    /// Converts a span in the decoded text to a span in the original file.
+    ///
+    /// # Arguments
+    ///
+    /// * `span` - A span of a token produced from `text`
+    pub fn original_span(&self, span: Span) -> Span {
+        Span::new(self.original_offset(span.start), self.original_offset(span.end))
+    }
+
+    /// Appends a decoded character that took `width` bytes in the original.
+    fn push(&mut self, character: char, width: usize) {
+        self.text.push(character);
+        if character.len_utf8() != width {
+            let &(decoded, original) = self.checkpoints.last().expect("initial checkpoint");
+            let end = self.text.len();
+            // Characters since the last checkpoint had equal widths
+            let original_end = original + (end - character.len_utf8() - decoded) + width;
+            self.checkpoints.push((end, original_end));
+        }
+    }
+}
+
+/// Reads and decodes a source file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file
+/// * `encoding` - The encoding of the file, if known; see `SourceText::decode`
+///
+/// # Returns
+///
+/// * `Result<SourceText, TokenizerError>` - The decoded text, or an error if
+///   the file cannot be read or decoded.
+pub fn read_source(path: &Path, encoding: Option<Encoding>) -> Result<SourceText, TokenizerError> {
+    let bytes =
+        fs::read(path).map_err(|e| TokenizerError::IoError(format!("{}: {}", path.display(), e)))?;
+    SourceText::decode(&bytes, encoding).map_err(|e| match e {
+        TokenizerError::EncodingError(message) => {
+            TokenizerError::EncodingError(format!("{}: {}", path.display(), message))
+        }
+        other => other,
+    })
+}
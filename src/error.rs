@@ -45,6 +45,14 @@ pub enum TokenizerError {
     #[error("Invalid token: {0}")]
     InvalidToken(String),
 
+    /// Error occurred while reading a source file.
+    #[error("IO error: {0}")]
+    IoError(String),
+
+    /// A source file is not valid in its character encoding.
+    #[error("Encoding error: {0}")]
+    EncodingError(String),
+
     /// A general tokenization error occurred.
     #[error("Tokenization error: {0}")]
     TokenizationError(String),
@@ -9,6 +9,13 @@
 //! - `error`: Defines custom error types for configuration and tokenization.
 //! - `config`: Handles loading and parsing of tokenizer configurations.
 //! - `tokenizer`: Contains the core tokenization logic.
+//! - `parser`: Builds a concrete syntax tree from the token stream.
+//! - `encoding`: Decodes source files in SAP code pages and UTF-16.
+//! - `diagnostic`: Defines non-fatal problems reported while processing code.
+//! - `analysis`: Extracts information such as text symbol usages from tokens.
+//! - `doc`: Extracts ABAP Doc comments and generates API documentation.
+//! - `preprocess`: Rewrites the token stream, for example expanding macros.
+//! - `abapgit`: Reads abapGit repositories as objects with metadata.
 //!
 //! ## Usage
 //! To use this library, typically you would:
@@ -17,15 +24,19 @@
 //! 3. Use the `next_token` method to tokenize your code
 //!
 //! ### Example:
-//! ```
+//! ```no_run
 //! use abap_tokenizer::config::load_toml_config;
 //! use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
 //!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let input = "WRITE 'Hello'.";
 //! let config = load_toml_config("path/to/config.toml")?;
 //! let mut tokenizer = FlexibleTokenizer::new(input, config);
 //! while let Ok(Some(token)) = tokenizer.next_token() {
 //!     println!("{:?}", token);
 //! }
+//! # Ok(())
+//! # }
 //! ```
 
 /// Error handling module for the ABAP Tokenizer.
@@ -37,5 +48,29 @@ pub mod config;
 /// Core tokenization module for the ABAP Tokenizer.
 pub mod tokenizer;
 
+/// Concrete syntax tree parser for ABAP code.
+pub mod parser;
+
+/// Character encodings of source files.
+pub mod encoding;
+
+/// Diagnostics reported while processing ABAP code.
+pub mod diagnostic;
+
+/// Analyses of tokenized ABAP code.
+pub mod analysis;
+
+/// ABAP Doc extraction and documentation generation.
+pub mod doc;
+
+/// Token stream preprocessing such as macro expansion.
+pub mod preprocess;
+
+/// abapGit repository reading.
+pub mod abapgit;
+
 /// Re-export of error types for easier access by users of this library.
-pub use error::{ConfigError, TokenizerError};
\ No newline at end of file
+pub use error::{ConfigError, TokenizerError};
+
+/// Re-export of the diagnostic type for easier access by users of this library.
+pub use diagnostic::{Diagnostic, Severity};
\ No newline at end of file
@@ -1,38 +1,289 @@
 // abap-tokenizer/src/main.rs
-use abap_tokenizer::config::load_toml_config;
+use abap_tokenizer::abapgit::{read_dynpros, AbapGitRepository};
+use abap_tokenizer::analysis::annotations::{annotations_in_file, AnnotationKind};
+use abap_tokenizer::analysis::min_release::min_release_in_file;
+use abap_tokenizer::analysis::text_symbols::find_text_symbols;
+use abap_tokenizer::config::{load_toml_config, Dialect, TokenizerConfig};
+use abap_tokenizer::doc::{docs_in_tree, DocFormat};
+use abap_tokenizer::encoding::{read_source, Encoding};
+use abap_tokenizer::error::ConfigError;
+use abap_tokenizer::preprocess::includes::IncludeResolver;
 use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use clap::{Parser, Subcommand, ValueEnum};
+use std::fs;
 use std::path::Path;
 
+/// Tokenizador flexible de código ABAP.
+#[derive(Parser)]
+#[command(name = "abap-tokenizer", version, about)]
+struct Cli {
+    /// Archivo de configuración TOML del tokenizador. Sin esta opción ni
+    /// `--dialect` el perfil integrado se elige por la extensión del archivo:
+    /// CDS (`.asddls`), definición de comportamiento (`.asbdef`), lógica de
+    /// flujo de pantallas (`.screen_*.xml`) o, para los demás, ABAP 7.50.
+    #[arg(long, global = true, conflicts_with = "dialect")]
+    config: Option<String>,
+
+    /// Perfil integrado de una versión de ABAP (7.02, 7.40, 7.50, 7.5x, cloud)
+    /// o de otro lenguaje (cds, bdef, dynpro).
+    #[arg(long, global = true)]
+    dialect: Option<Dialect>,
+
+    /// Codificación de los archivos fuente (utf-8, iso-8859-1, windows-1252,
+    /// utf-16le, utf-16be). Sustituye a la de la configuración; una marca de
+    /// orden de bytes (BOM) en el archivo tiene prioridad.
+    #[arg(long, global = true)]
+    encoding: Option<Encoding>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Muestra los tokens de un archivo ABAP.
+    Tokenize {
+        /// Archivo ABAP a tokenizar.
+        file: String,
+    },
+    /// Muestra los tokens de un programa con sus includes resueltos.
+    Resolve {
+        /// Programa principal.
+        file: String,
+        /// Directorio en el que se buscan los includes; por defecto, el del programa.
+        #[arg(long)]
+        root: Option<String>,
+    },
+    /// Lista los objetos de un repositorio abapGit con sus partes y sus símbolos de texto.
+    Objects {
+        /// Directorio del repositorio, normalmente su carpeta `src`.
+        root: String,
+    },
+    /// Lista los pragmas y pseudo-comentarios de cada archivo con la sentencia a la que se aplican.
+    Annotations {
+        /// Archivos ABAP a analizar.
+        #[arg(required = true)]
+        files: Vec<String>,
+    },
+    /// Determina la release mínima de ABAP que requiere cada archivo y dónde se fija.
+    MinRelease {
+        /// Archivos ABAP a analizar.
+        #[arg(required = true)]
+        files: Vec<String>,
+        /// Muestra todas las características detectadas, no solo las que fijan la release.
+        #[arg(long)]
+        all: bool,
+    },
+    /// Genera la documentación de la API a partir de los comentarios ABAP Doc.
+    Doc {
+        /// Directorio con el código fuente (archivos `.abap`) o un único archivo.
+        path: String,
+        /// Formato de salida.
+        #[arg(long, value_enum, default_value_t = OutputFormat::Markdown)]
+        format: OutputFormat,
+        /// Archivo de salida; por defecto, la salida estándar.
+        #[arg(long)]
+        output: Option<String>,
+    },
+}
+
+/// Formatos de la documentación generada.
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Markdown,
+    Html,
+}
+
+impl From<OutputFormat> for DocFormat {
+    fn from(format: OutputFormat) -> Self {
+        match format {
+            OutputFormat::Markdown => DocFormat::Markdown,
+            OutputFormat::Html => DocFormat::Html,
+        }
+    }
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Cargar la configuración
-    let config_path = Path::new("config/default_config.toml");
-    let config = load_toml_config(config_path.to_str().unwrap())?;
-
-    // Código ABAP de ejemplo
-    let input = 
- r#"REPORT z_test_program. "inicio
-*   Esto es un comentario de linea.
-    DATA: lv_number TYPE i VALUE 10,
-          lv_text   TYPE string VALUE 'Hello, ABAP!'.
-
-    WRITE: 'Number:', lv_number. "Comentario abap entre lineas
-    WRITE: / 'Text:', lv_text.
-
-    IF lv_number > 5.
-      WRITE: / 'Number is greater than 5'.
-    ELSE.
-      WRITE: / 'Number is less than or equal to 5'.
-    ENDIF.
-    "#;
-
-    // Crear el tokenizador
-    let mut tokenizer = FlexibleTokenizer::new(input, config);
-
-    // Tokenizar y mostrar los resultados
-    println!("Tokens encontrados:");
-    while let Ok(Some(token)) = tokenizer.next_token() {
-        println!("{:?}", token);
+    env_logger::init();
+    let cli = Cli::parse();
+
+    // Cargar la configuración indicada; sin --config ni --dialect, el perfil
+    // se elige para cada archivo según su extensión
+    let selected = match (&cli.config, cli.dialect) {
+        (Some(path), _) => Some(load_toml_config(path)?),
+        (None, Some(dialect)) => Some(dialect.config()?),
+        (None, None) => None,
+    };
+    let config_for = |path: &str| -> Result<TokenizerConfig, ConfigError> {
+        let mut config = match &selected {
+            Some(config) => config.clone(),
+            None => Dialect::for_file(Path::new(path))
+                .unwrap_or_default()
+                .config()?,
+        };
+        if let Some(encoding) = cli.encoding {
+            config.metadata.encoding = Some(encoding);
+        }
+        Ok(config)
+    };
+
+    match cli.command {
+        Command::Tokenize { file } => {
+            let config = config_for(&file)?;
+            let path = Path::new(&file);
+            // La lógica de flujo de las pantallas está dentro del XML de abapGit
+            let sources = if Dialect::for_file(path) == Some(Dialect::Dynpro) {
+                read_dynpros(path, config.metadata.encoding)?
+                    .into_iter()
+                    .map(|dynpro| (Some(dynpro.number), dynpro.flow_logic))
+                    .collect()
+            } else {
+                vec![(None, read_source(path, config.metadata.encoding)?.text)]
+            };
+            for (screen, text) in sources {
+                if let Some(screen) = screen {
+                    println!("== pantalla {}", screen);
+                }
+                let mut tokenizer = FlexibleTokenizer::new(&text, config.clone());
+                for token in tokenizer.tokenize()? {
+                    println!(
+                        "{}:{}\t{}\t{:?}",
+                        token.line, token.column, token.token_type, token.value
+                    );
+                }
+                // Los diagnósticos se muestran en la salida de errores
+                for diagnostic in tokenizer.diagnostics() {
+                    eprintln!("{}:{}", file, diagnostic);
+                }
+            }
+        }
+        Command::Resolve { file, root } => {
+            let config = config_for(&file)?;
+            let file = Path::new(&file);
+            let root = match &root {
+                Some(root) => Path::new(root),
+                None => file.parent().unwrap_or(Path::new(".")),
+            };
+            let program = IncludeResolver::new(root, config)?.resolve(file)?;
+            let file_name = |file_id| {
+                program
+                    .source_map
+                    .file(file_id)
+                    .map_or(String::new(), |source| source.path.display().to_string())
+            };
+            for token in &program.tokens {
+                println!(
+                    "{}:{}:{}\t{}\t{:?}",
+                    file_name(token.file_id),
+                    token.line,
+                    token.column,
+                    token.token_type,
+                    token.value
+                );
+            }
+            // Los diagnósticos se muestran en la salida de errores
+            for diagnostic in &program.diagnostics {
+                eprintln!("{}:{}", file_name(diagnostic.file_id), diagnostic);
+            }
+        }
+        Command::Objects { root } => {
+            let config = config_for(&root)?;
+            let repository = AbapGitRepository::scan(Path::new(&root), config.metadata.encoding)?;
+            for object in &repository.objects {
+                let metadata = object.metadata.clone().unwrap_or_default();
+                println!(
+                    "{}\t{}",
+                    object,
+                    metadata.description.as_deref().unwrap_or_default()
+                );
+                for part in object.tokenize(&config)? {
+                    println!(
+                        "  {}\t{} tokens",
+                        part.part.as_deref().unwrap_or("main"),
+                        part.tokens.len()
+                    );
+                    // Símbolos de texto usados que no están en el pool de textos
+                    for usage in find_text_symbols(&part.tokens) {
+                        if metadata.text_symbol(&usage.key).is_none() {
+                            println!(
+                                "    {}:{}: TEXT-{} no está en el pool de textos",
+                                usage.line, usage.column, usage.key
+                            );
+                        }
+                    }
+                }
+            }
+        }
+        Command::MinRelease { files, all } => {
+            for file in files {
+                let report = min_release_in_file(&file, config_for(&file)?)?;
+                let Some(minimum) = report.minimum else {
+                    println!("{}: sin características dependientes de la release", report.file);
+                    continue;
+                };
+                println!("{}: release mínima {}", report.file, minimum);
+                let usages: Vec<_> = if all {
+                    report.usages.iter().collect()
+                } else {
+                    report.pinning().collect()
+                };
+                for usage in usages {
+                    println!(
+                        "{}:{}:{}: {} {:?} ({})",
+                        report.file,
+                        usage.line,
+                        usage.column,
+                        usage.feature.description(),
+                        usage.text,
+                        usage.feature.release()
+                    );
+                }
+            }
+        }
+        Command::Annotations { files } => {
+            for file in files {
+                let report = annotations_in_file(&file, config_for(&file)?)?;
+                for statement in &report.statements {
+                    for annotation in &statement.annotations {
+                        let text = match annotation.kind {
+                            AnnotationKind::Pragma => {
+                                let parameters: String = annotation
+                                    .parameters
+                                    .iter()
+                                    .map(|parameter| format!("[{}]", parameter))
+                                    .collect();
+                                format!("pragma ##{}{}", annotation.code, parameters)
+                            }
+                            AnnotationKind::PseudoComment => {
+                                format!("pseudo-comment \"#EC {}", annotation.code)
+                            }
+                        };
+                        println!(
+                            "{}:{}:{}: {} (statement at {}:{})",
+                            report.file,
+                            annotation.line,
+                            annotation.column,
+                            text,
+                            statement.line,
+                            statement.column
+                        );
+                    }
+                }
+            }
+        }
+        Command::Doc {
+            path,
+            format,
+            output,
+        } => {
+            let files = docs_in_tree(Path::new(&path), &config_for(&path)?)?;
+            let document = DocFormat::from(format).render(&files);
+            match output {
+                Some(output) => fs::write(output, document)?,
+                None => print!("{}", document),
+            }
+        }
     }
 
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,42 @@
+// abap-tokenizer/src/parser/mod.rs
+//! Parsing module for the ABAP Tokenizer.
+//!
+//! This module builds a lossless concrete syntax tree on top of the tokens
+//! produced by `FlexibleTokenizer`. It recognises the common statement families
+//! (declarations, assignments, method calls, control structures, Open SQL and
+//! class/method definitions) and recovers from errors at statement boundaries.
+
+/// Defines the nodes, tokens and trivia of the concrete syntax tree.
+pub mod syntax_tree;
+
+/// Contains the statement-based parser building the syntax tree.
+pub mod statement_parser;
+
+pub use statement_parser::Parser;
+pub use syntax_tree::{
+    SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, SyntaxTree, Trivia, TriviaKind,
+};
+
+use crate::config::TokenizerConfig;
+use crate::error::TokenizerError;
+use crate::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+
+/// Tokenizes and parses ABAP code into a concrete syntax tree.
+///
+/// # Arguments
+///
+/// * `input` - The ABAP code to parse
+/// * `config` - The TokenizerConfig to use for tokenization
+///
+/// # Returns
+///
+/// * `Result<SyntaxTree, TokenizerError>` - The syntax tree, or an error if tokenization fails.
+///   Syntax errors do not fail parsing; they are reported in `SyntaxTree::diagnostics`,
+///   after the problems found by the tokenizer, such as unterminated string templates and block comments.
+pub fn parse(input: &str, config: TokenizerConfig) -> Result<SyntaxTree, TokenizerError> {
+    let mut tokenizer = FlexibleTokenizer::new(input, config);
+    let tokens = tokenizer.tokenize()?;
+    let mut tree = Parser::new(input, tokens).parse();
+    tree.diagnostics.splice(0..0, tokenizer.diagnostics().iter().cloned());
+    Ok(tree)
+}
@@ -0,0 +1,665 @@
+// abap-tokenizer/src/parser/statement_parser.rs
+//! Statement-based parser producing a concrete syntax tree.
+//!
+//! ABAP code is a sequence of statements terminated by a period. The parser
+//! first splits the token stream into statements, then nests them into blocks
+//! (`IF ... ENDIF`, `CLASS ... ENDCLASS`, ...) and finally classifies each
+//! statement. Because every decision is made at statement granularity, a
+//! malformed statement only produces an error node and a diagnostic; parsing
+//! resumes with the next statement. Tokens lexed in an embedded-language mode,
+//! such as native SQL, are not split at periods and form a single
+//! `EmbeddedCode` node instead.
+use super::syntax_tree::{
+    SyntaxElement, SyntaxKind, SyntaxNode, SyntaxToken, SyntaxTree, Trivia, TriviaKind,
+};
+use crate::config::tokenizer_config::MAIN_MODE;
+use crate::diagnostic::Diagnostic;
+use crate::tokenizer::token::Token;
+use log::debug;
+
+/// Assignment operators recognised after the target of an assignment.
+const ASSIGNMENT_OPERATORS: &[&str] = &["=", "?=", "+=", "-=", "*=", "/=", "&&="];
+
+/// Keywords starting a clause of an Open SQL statement. A clause starts at
+/// a token whose first word is one of them, such as `ORDER` in `ORDER BY`.
+const SQL_CLAUSE_KEYWORDS: &[&str] = &[
+    "SELECT",
+    "FROM",
+    "FIELDS",
+    "WHERE",
+    "INTO",
+    "APPENDING",
+    "GROUP",
+    "HAVING",
+    "ORDER",
+    "UP",
+    "FOR",
+];
+
+/// Aggregate functions that make a `SELECT` return a single row.
+const SQL_AGGREGATES: &[&str] = &["COUNT", "COUNT(*)", "SUM", "MAX", "MIN", "AVG"];
+
+/// Description of a block construct: how it ends and which branches it has.
+struct BlockSpec {
+    /// Kind of the block node.
+    kind: SyntaxKind,
+    /// Keyword of the statement closing the block.
+    end: &'static str,
+    /// Keywords of statements opening a branch, with the kind of the branch node.
+    branches: &'static [(&'static str, SyntaxKind)],
+}
+
+const IF_BLOCK: BlockSpec = BlockSpec {
+    kind: SyntaxKind::IfBlock,
+    end: "ENDIF",
+    branches: &[
+        ("ELSEIF", SyntaxKind::ElseIfBranch),
+        ("ELSE", SyntaxKind::ElseBranch),
+    ],
+};
+const CASE_BLOCK: BlockSpec = BlockSpec {
+    kind: SyntaxKind::CaseBlock,
+    end: "ENDCASE",
+    branches: &[("WHEN", SyntaxKind::WhenBranch)],
+};
+const LOOP_BLOCK: BlockSpec = BlockSpec {
+    kind: SyntaxKind::LoopBlock,
+    end: "ENDLOOP",
+    branches: &[],
+};
+const DO_BLOCK: BlockSpec = BlockSpec {
+    kind: SyntaxKind::DoBlock,
+    end: "ENDDO",
+    branches: &[],
+};
+const WHILE_BLOCK: BlockSpec = BlockSpec {
+    kind: SyntaxKind::WhileBlock,
+    end: "ENDWHILE",
+    branches: &[],
+};
+const SELECT_LOOP: BlockSpec = BlockSpec {
+    kind: SyntaxKind::SelectLoop,
+    end: "ENDSELECT",
+    branches: &[],
+};
+const TRY_BLOCK: BlockSpec = BlockSpec {
+    kind: SyntaxKind::TryBlock,
+    end: "ENDTRY",
+    branches: &[
+        ("CATCH", SyntaxKind::CatchBranch),
+        ("CLEANUP", SyntaxKind::CatchBranch),
+    ],
+};
+const CLASS_DEFINITION: BlockSpec = BlockSpec {
+    kind: SyntaxKind::ClassDefinition,
+    end: "ENDCLASS",
+    branches: &[],
+};
+const CLASS_IMPLEMENTATION: BlockSpec = BlockSpec {
+    kind: SyntaxKind::ClassImplementation,
+    end: "ENDCLASS",
+    branches: &[],
+};
+const INTERFACE_DEFINITION: BlockSpec = BlockSpec {
+    kind: SyntaxKind::InterfaceDefinition,
+    end: "ENDINTERFACE",
+    branches: &[],
+};
+const METHOD_IMPLEMENTATION: BlockSpec = BlockSpec {
+    kind: SyntaxKind::MethodImplementation,
+    end: "ENDMETHOD",
+    branches: &[],
+};
+
+/// All block specifications, used to recognise stray closing and branch statements.
+const ALL_BLOCKS: &[&BlockSpec] = &[
+    &IF_BLOCK,
+    &CASE_BLOCK,
+    &LOOP_BLOCK,
+    &DO_BLOCK,
+    &WHILE_BLOCK,
+    &SELECT_LOOP,
+    &TRY_BLOCK,
+    &CLASS_DEFINITION,
+    &CLASS_IMPLEMENTATION,
+    &INTERFACE_DEFINITION,
+    &METHOD_IMPLEMENTATION,
+];
+
+/// A statement as split from the token stream, before classification.
+struct RawStatement {
+    /// The tokens of the statement, including the terminating period.
+    tokens: Vec<SyntaxToken>,
+    /// Whether the statement ends with a period.
+    terminated: bool,
+    /// Whether the tokens were lexed in an embedded-language mode.
+    embedded: bool,
+}
+
+/// Parser building a concrete syntax tree from a token stream.
+pub struct Parser {
+    /// The statements of the input, in source order.
+    statements: Vec<RawStatement>,
+    /// Index of the next statement to be parsed.
+    position: usize,
+    /// Trivia after the last token of the input.
+    trailing_trivia: Vec<Trivia>,
+    /// Problems found while parsing.
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Parser {
+    /// Creates a new Parser instance.
+    ///
+    /// The tokens must come from tokenizing `input`; their spans are used to
+    /// recover the whitespace between them, and `Comment` tokens are turned
+    /// into trivia.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The ABAP code the tokens were produced from
+    /// * `tokens` - The tokens of the input, in source order
+    ///
+    /// # Returns
+    ///
+    /// A new Parser instance
+    pub fn new(input: &str, tokens: Vec<Token>) -> Self {
+        let (syntax_tokens, trailing_trivia) = Self::attach_trivia(input, tokens);
+        Parser {
+            statements: Self::split_statements(syntax_tokens),
+            position: 0,
+            trailing_trivia,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    /// Parses the statements into a syntax tree.
+    ///
+    /// # Returns
+    ///
+    /// The syntax tree, including the diagnostics found while parsing.
+    pub fn parse(mut self) -> SyntaxTree {
+        let children = self.parse_body(&[]);
+        SyntaxTree {
+            root: SyntaxNode::new(SyntaxKind::SourceFile, children),
+            trailing_trivia: self.trailing_trivia,
+            diagnostics: self.diagnostics,
+        }
+    }
+
+    /// Converts tokens into syntax tokens, attaching whitespace, comments and
+    /// pragmas as leading trivia.
+    fn attach_trivia(input: &str, tokens: Vec<Token>) -> (Vec<SyntaxToken>, Vec<Trivia>) {
+        let mut syntax_tokens = Vec::with_capacity(tokens.len());
+        let mut pending = Vec::new();
+        let mut cursor = 0;
+
+        for token in tokens {
+            let span = token.span;
+            if span.start > cursor && span.start <= input.len() {
+                pending.push(Trivia {
+                    kind: TriviaKind::Whitespace,
+                    text: input[cursor..span.start].to_string(),
+                });
+            }
+            let text = if !span.is_empty() && span.end <= input.len() {
+                input[span.start..span.end].to_string()
+            } else {
+                token.value.clone()
+            };
+            cursor = cursor.max(span.end);
+
+            let trivia_kind = match token.token_type.category.as_str() {
+                "Comment" => Some(TriviaKind::Comment),
+                "DocComment" => Some(TriviaKind::DocComment),
+                "Pragma" => Some(TriviaKind::Pragma),
+                _ => None,
+            };
+            if let Some(kind) = trivia_kind {
+                pending.push(Trivia { kind, text });
+            } else {
+                syntax_tokens.push(SyntaxToken {
+                    token,
+                    text,
+                    leading_trivia: std::mem::take(&mut pending),
+                });
+            }
+        }
+
+        if cursor < input.len() {
+            pending.push(Trivia {
+                kind: TriviaKind::Whitespace,
+                text: input[cursor..].to_string(),
+            });
+        }
+        (syntax_tokens, pending)
+    }
+
+    /// Splits syntax tokens into statements at each period.
+    ///
+    /// A run of tokens lexed outside the main mode becomes a statement of its
+    /// own, whatever periods it contains.
+    fn split_statements(tokens: Vec<SyntaxToken>) -> Vec<RawStatement> {
+        let mut statements = Vec::new();
+        let mut current: Vec<SyntaxToken> = Vec::new();
+        for token in tokens {
+            let embedded = token.token.mode != MAIN_MODE;
+            let run_changes = current
+                .last()
+                .is_some_and(|last| (last.token.mode != MAIN_MODE) != embedded);
+            if run_changes {
+                let current_embedded = !embedded;
+                statements.push(RawStatement {
+                    tokens: std::mem::take(&mut current),
+                    terminated: current_embedded,
+                    embedded: current_embedded,
+                });
+            }
+            let is_period = !embedded && token.symbol() == ".";
+            current.push(token);
+            if is_period {
+                statements.push(RawStatement {
+                    tokens: std::mem::take(&mut current),
+                    terminated: true,
+                    embedded: false,
+                });
+            }
+        }
+        if let Some(last) = current.last() {
+            let embedded = last.token.mode != MAIN_MODE;
+            statements.push(RawStatement {
+                tokens: current,
+                terminated: embedded,
+                embedded,
+            });
+        }
+        statements
+    }
+
+    /// Parses statements until the end of input or until a statement that
+    /// closes or branches one of the enclosing blocks.
+    fn parse_body(&mut self, enclosing: &[&'static BlockSpec]) -> Vec<SyntaxElement> {
+        let mut items = Vec::new();
+        while let Some(statement) = self.statements.get(self.position) {
+            let head = head_word(&statement.tokens);
+            if enclosing.iter().any(|spec| ends_or_branches(spec, &head)) {
+                break;
+            }
+            if let Some(spec) = block_spec(statement) {
+                items.push(SyntaxElement::Node(self.parse_block(spec, enclosing)));
+                continue;
+            }
+            if ALL_BLOCKS.iter().any(|spec| ends_or_branches(spec, &head)) {
+                let statement = self.next_statement();
+                let token = &statement.tokens[0];
+                self.diagnostics.push(Diagnostic::error(
+                    format!("unexpected {} without matching opening statement", head),
+                    token.token.line,
+                    token.token.column,
+                ));
+                items.push(SyntaxElement::Node(SyntaxNode::new(
+                    SyntaxKind::Error,
+                    wrap_tokens(statement.tokens),
+                )));
+                continue;
+            }
+            let statement = self.next_statement();
+            items.push(SyntaxElement::Node(self.build_statement(statement)));
+        }
+        items
+    }
+
+    /// Parses a block whose opening statement is the next statement.
+    fn parse_block(
+        &mut self,
+        spec: &'static BlockSpec,
+        enclosing: &[&'static BlockSpec],
+    ) -> SyntaxNode {
+        let opening = self.next_statement();
+        let (line, column) = opening
+            .tokens
+            .first()
+            .map_or((0, 0), |t| (t.token.line, t.token.column));
+        let opening_word = head_word(&opening.tokens);
+        let mut children = vec![SyntaxElement::Node(self.build_statement(opening))];
+
+        let mut frames = enclosing.to_vec();
+        frames.push(spec);
+        children.extend(self.parse_body(&frames));
+
+        loop {
+            let head = match self.statements.get(self.position) {
+                Some(statement) => head_word(&statement.tokens),
+                None => String::new(),
+            };
+            if head == spec.end {
+                let closing = self.next_statement();
+                children.push(SyntaxElement::Node(self.build_statement(closing)));
+                break;
+            }
+            if let Some(&(_, branch_kind)) = spec.branches.iter().find(|(kw, _)| *kw == head) {
+                let branch = self.next_statement();
+                let mut branch_children = vec![SyntaxElement::Node(self.build_statement(branch))];
+                branch_children.extend(self.parse_body(&frames));
+                children.push(SyntaxElement::Node(SyntaxNode::new(
+                    branch_kind,
+                    branch_children,
+                )));
+                continue;
+            }
+            debug!(
+                "Block {} at {}:{} is not closed",
+                opening_word, line, column
+            );
+            self.diagnostics.push(Diagnostic::error(
+                format!("missing {} for {} started here", spec.end, opening_word),
+                line,
+                column,
+            ));
+            break;
+        }
+        SyntaxNode::new(spec.kind, children)
+    }
+
+    /// Returns the next statement and advances past it.
+    fn next_statement(&mut self) -> RawStatement {
+        let statement = std::mem::replace(
+            &mut self.statements[self.position],
+            RawStatement {
+                tokens: Vec::new(),
+                terminated: true,
+                embedded: false,
+            },
+        );
+        self.position += 1;
+        statement
+    }
+
+    /// Classifies a single statement and builds its node.
+    fn build_statement(&mut self, statement: RawStatement) -> SyntaxNode {
+        if statement.embedded {
+            return SyntaxNode::new(SyntaxKind::EmbeddedCode, wrap_tokens(statement.tokens));
+        }
+        let kind = classify_statement(&statement.tokens);
+        let children = if kind == SyntaxKind::SelectStatement {
+            group_sql_clauses(statement.tokens)
+        } else {
+            group_inline_declarations(statement.tokens)
+        };
+        let node = SyntaxNode::new(kind, children);
+
+        if statement.terminated {
+            return node;
+        }
+        if let Some(token) = node.first_token() {
+            self.diagnostics.push(Diagnostic::error(
+                "statement is not terminated by a period",
+                token.token.line,
+                token.token.column,
+            ));
+        }
+        SyntaxNode::new(SyntaxKind::Error, vec![SyntaxElement::Node(node)])
+    }
+}
+
+/// Returns true if `head` closes the block or opens one of its branches.
+fn ends_or_branches(spec: &BlockSpec, head: &str) -> bool {
+    spec.end == head || spec.branches.iter().any(|(kw, _)| *kw == head)
+}
+
+/// Returns the block a statement opens, if any.
+fn block_spec(statement: &RawStatement) -> Option<&'static BlockSpec> {
+    let words = statement_words(&statement.tokens);
+    let head = words.first().map(String::as_str).unwrap_or("");
+    let has = |word: &str| words.iter().any(|w| w == word);
+    // `CLASS ... DEFINITION LOCAL FRIENDS ...` grants friendship without a body
+    let local_friends = words.windows(2).any(|pair| pair[0] == "LOCAL" && pair[1] == "FRIENDS");
+    match head {
+        "IF" => Some(&IF_BLOCK),
+        "CASE" => Some(&CASE_BLOCK),
+        "LOOP" => Some(&LOOP_BLOCK),
+        "DO" => Some(&DO_BLOCK),
+        "WHILE" => Some(&WHILE_BLOCK),
+        "TRY" => Some(&TRY_BLOCK),
+        "METHOD" => Some(&METHOD_IMPLEMENTATION),
+        "SELECT" if is_select_loop(&words) => Some(&SELECT_LOOP),
+        "CLASS" if has("DEFERRED") || has("LOAD") || local_friends => None,
+        "CLASS" if has("DEFINITION") => Some(&CLASS_DEFINITION),
+        "CLASS" if has("IMPLEMENTATION") => Some(&CLASS_IMPLEMENTATION),
+        "INTERFACE" if has("DEFERRED") || has("LOAD") => None,
+        "INTERFACE" => Some(&INTERFACE_DEFINITION),
+        _ => None,
+    }
+}
+
+/// Returns true if a `SELECT` statement opens a loop closed by `ENDSELECT`.
+///
+/// A `SELECT` reads a single row, and therefore needs no `ENDSELECT`, when it
+/// uses `SINGLE`, writes into an internal table, or only selects aggregates.
+fn is_select_loop(words: &[String]) -> bool {
+    let single = words.get(1).is_some_and(|w| w == "SINGLE");
+    let into_table = words.iter().any(|w| w == "TABLE");
+    let aggregate = words
+        .get(1)
+        .is_some_and(|w| SQL_AGGREGATES.contains(&w.as_str()));
+    !(single || into_table || aggregate)
+}
+
+/// Determines the kind of a statement from its tokens.
+fn classify_statement(tokens: &[SyntaxToken]) -> SyntaxKind {
+    let words = statement_words(tokens);
+    let head = words.first().map(String::as_str).unwrap_or("");
+    let inline = starts_inline_declaration(tokens, 0);
+
+    match head {
+        "DATA" | "CLASS-DATA" | "STATICS" if !inline => SyntaxKind::DataDeclaration,
+        "TYPES" => SyntaxKind::TypeDeclaration,
+        "CONSTANTS" => SyntaxKind::ConstantDeclaration,
+        "FIELD-SYMBOLS" => SyntaxKind::FieldSymbolDeclaration,
+        "METHODS" | "CLASS-METHODS" => SyntaxKind::MethodDeclaration,
+        "SELECT" => SyntaxKind::SelectStatement,
+        "CALL" if words.get(1).is_some_and(|w| w == "METHOD") => SyntaxKind::MethodCall,
+        _ if is_assignment(tokens) => SyntaxKind::Assignment,
+        _ if is_method_call(tokens) => SyntaxKind::MethodCall,
+        _ => SyntaxKind::Statement,
+    }
+}
+
+/// Returns true if a statement is an assignment.
+///
+/// The target of an assignment is a single operand written without whitespace
+/// (`ls_data-field`, `lo_obj->attr`, `DATA(lv_x)`), followed by an assignment
+/// operator. Keyword statements such as `IF a = b.` fail this test because
+/// their first word is separated from the rest by whitespace.
+fn is_assignment(tokens: &[SyntaxToken]) -> bool {
+    let target_end = operand_end(tokens, 0);
+    if target_end == 0 || tokens[0].symbol() == "." {
+        return false;
+    }
+    let mut operator = String::new();
+    let mut index = target_end;
+    while let Some(token) = tokens.get(index) {
+        if index > target_end && !is_adjacent(&tokens[index - 1], token) {
+            break;
+        }
+        operator.push_str(&token.text);
+        if ASSIGNMENT_OPERATORS.contains(&operator.as_str()) {
+            return true;
+        }
+        if operator.len() >= 3 {
+            break;
+        }
+        index += 1;
+    }
+    false
+}
+
+/// Returns true if a statement consists of a single functional method call.
+fn is_method_call(tokens: &[SyntaxToken]) -> bool {
+    let significant = match tokens.last() {
+        Some(last) if last.symbol() == "." => &tokens[..tokens.len() - 1],
+        _ => tokens,
+    };
+    if significant.is_empty() || operand_end(significant, 0) != significant.len() {
+        return false;
+    }
+    significant.iter().any(|t| t.symbol() == "(") && significant.last().is_some_and(|t| t.symbol() == ")")
+}
+
+/// Returns the index just past the operand starting at `start`.
+///
+/// An operand extends over adjacent tokens and over everything enclosed in
+/// parentheses or brackets, so `lo_obj->method( a = 1 )` is a single operand.
+fn operand_end(tokens: &[SyntaxToken], start: usize) -> usize {
+    let mut depth = 0usize;
+    let mut index = start;
+    while let Some(token) = tokens.get(index) {
+        if index > start && depth == 0 && !is_adjacent(&tokens[index - 1], token) {
+            break;
+        }
+        match token.symbol() {
+            "(" | "[" => depth += 1,
+            ")" | "]" => depth = depth.saturating_sub(1),
+            "." if depth == 0 => break,
+            _ => {}
+        }
+        index += 1;
+    }
+    index
+}
+
+/// Returns true if there is no whitespace or comment between two tokens.
+fn is_adjacent(previous: &SyntaxToken, next: &SyntaxToken) -> bool {
+    next.leading_trivia.is_empty() && previous.token.span.end == next.token.span.start
+}
+
+/// Returns true if an inline declaration `DATA(` or `FINAL(` starts at `index`.
+fn starts_inline_declaration(tokens: &[SyntaxToken], index: usize) -> bool {
+    match (tokens.get(index), tokens.get(index + 1)) {
+        (Some(keyword), Some(paren)) => {
+            matches!(keyword.upper().as_str(), "DATA" | "FINAL")
+                && paren.symbol() == "("
+                && is_adjacent(keyword, paren)
+        }
+        _ => false,
+    }
+}
+
+/// Returns the upper-case leading word of a statement.
+fn head_word(tokens: &[SyntaxToken]) -> String {
+    statement_words(tokens)
+        .into_iter()
+        .next()
+        .unwrap_or_default()
+}
+
+/// Returns the upper-case words of a statement.
+///
+/// Words joined by adjacent hyphens, such as `CLASS-DATA` when it is lexed as
+/// three tokens, are merged into a single word, and keyword phrases such as
+/// `LOOP AT` are split into their words. Embedded code has no ABAP words.
+fn statement_words(tokens: &[SyntaxToken]) -> Vec<String> {
+    if tokens.first().is_some_and(|token| token.token.mode != MAIN_MODE) {
+        return Vec::new();
+    }
+    let mut words: Vec<String> = Vec::new();
+    let mut index = 0;
+    while index < tokens.len() {
+        let mut word = tokens[index].upper();
+        while index + 2 < tokens.len()
+            && tokens[index + 1].symbol() == "-"
+            && is_adjacent(&tokens[index], &tokens[index + 1])
+            && is_adjacent(&tokens[index + 1], &tokens[index + 2])
+        {
+            word.push('-');
+            word.push_str(&tokens[index + 2].upper());
+            index += 2;
+        }
+        words.extend(word.split_whitespace().map(String::from));
+        index += 1;
+    }
+    words
+}
+
+/// Wraps tokens into syntax elements without further structure.
+fn wrap_tokens(tokens: Vec<SyntaxToken>) -> Vec<SyntaxElement> {
+    tokens.into_iter().map(SyntaxElement::Token).collect()
+}
+
+/// Builds statement children, grouping inline declarations into nodes.
+fn group_inline_declarations(tokens: Vec<SyntaxToken>) -> Vec<SyntaxElement> {
+    let mut children = Vec::with_capacity(tokens.len());
+    let mut index = 0;
+    let starts: Vec<bool> = (0..tokens.len())
+        .map(|i| starts_inline_declaration(&tokens, i))
+        .collect();
+    let mut iter = tokens.into_iter();
+
+    while let Some(token) = iter.next() {
+        if !starts[index] {
+            children.push(SyntaxElement::Token(token));
+            index += 1;
+            continue;
+        }
+        let mut declaration = vec![SyntaxElement::Token(token)];
+        index += 1;
+        for token in iter.by_ref() {
+            index += 1;
+            let closes = token.symbol() == ")";
+            declaration.push(SyntaxElement::Token(token));
+            if closes {
+                break;
+            }
+        }
+        children.push(SyntaxElement::Node(SyntaxNode::new(
+            SyntaxKind::InlineDeclaration,
+            declaration,
+        )));
+    }
+    children
+}
+
+/// Builds the children of an Open SQL statement, grouping its clauses.
+///
+/// A new clause starts at each clause keyword outside parentheses; the
+/// terminating period stays outside of the clauses.
+fn group_sql_clauses(tokens: Vec<SyntaxToken>) -> Vec<SyntaxElement> {
+    let mut clauses: Vec<Vec<SyntaxToken>> = Vec::new();
+    let mut period = None;
+    let mut depth = 0usize;
+
+    for token in tokens {
+        match token.symbol() {
+            "(" | "[" => depth += 1,
+            ")" | "]" => depth = depth.saturating_sub(1),
+            "." if depth == 0 => {
+                period = Some(token);
+                continue;
+            }
+            _ => {}
+        }
+        let upper = token.upper();
+        let first_word = upper.split_whitespace().next().unwrap_or_default();
+        let starts_clause = depth == 0
+            && token.token.token_type.category != "Literal"
+            && SQL_CLAUSE_KEYWORDS.contains(&first_word);
+        match clauses.last_mut() {
+            Some(clause) if !starts_clause => clause.push(token),
+            _ => clauses.push(vec![token]),
+        }
+    }
+
+    let mut children: Vec<SyntaxElement> = clauses
+        .into_iter()
+        .map(|clause| {
+            SyntaxElement::Node(SyntaxNode::new(
+                SyntaxKind::SqlClause,
+                group_inline_declarations(clause),
+            ))
+        })
+        .collect();
+    if let Some(period) = period {
+        children.push(SyntaxElement::Token(period));
+    }
+    children
+}
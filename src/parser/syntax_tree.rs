@@ -0,0 +1,253 @@
+// abap-tokenizer/src/parser/syntax_tree.rs
+//! Concrete syntax tree structures for ABAP code.
+//!
+//! The tree is lossless: every byte of the input is owned either by a token or
+//! by the trivia (whitespace, comments and pragmas) preceding it, so printing the tree
+//! reproduces the original source exactly.
+use crate::diagnostic::Diagnostic;
+use crate::tokenizer::token::Token;
+use std::fmt;
+
+/// The kind of a syntax node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SyntaxKind {
+    /// Root node covering a whole source file.
+    SourceFile,
+
+    /// `DATA`, `CLASS-DATA` or `STATICS` declaration.
+    DataDeclaration,
+    /// `TYPES` declaration.
+    TypeDeclaration,
+    /// `CONSTANTS` declaration.
+    ConstantDeclaration,
+    /// `FIELD-SYMBOLS` declaration.
+    FieldSymbolDeclaration,
+    /// `METHODS` or `CLASS-METHODS` declaration inside a class or interface.
+    MethodDeclaration,
+    /// Assignment such as `lv_a = lv_b + 1.` or `DATA(lv_a) = 1.`.
+    Assignment,
+    /// Standalone method call such as `lo_obj->run( ).` or `CALL METHOD ...`.
+    MethodCall,
+    /// Open SQL `SELECT` statement that does not open a loop.
+    SelectStatement,
+    /// Any other complete statement.
+    Statement,
+
+    /// `IF ... ENDIF.` block.
+    IfBlock,
+    /// `ELSEIF` branch of an `IF` block.
+    ElseIfBranch,
+    /// `ELSE` branch of an `IF` block.
+    ElseBranch,
+    /// `CASE ... ENDCASE.` block.
+    CaseBlock,
+    /// `WHEN` branch of a `CASE` block.
+    WhenBranch,
+    /// `LOOP ... ENDLOOP.` block.
+    LoopBlock,
+    /// `DO ... ENDDO.` block.
+    DoBlock,
+    /// `WHILE ... ENDWHILE.` block.
+    WhileBlock,
+    /// `SELECT ... ENDSELECT.` loop.
+    SelectLoop,
+    /// `TRY ... ENDTRY.` block.
+    TryBlock,
+    /// `CATCH` or `CLEANUP` branch of a `TRY` block.
+    CatchBranch,
+    /// `CLASS ... DEFINITION ... ENDCLASS.` block.
+    ClassDefinition,
+    /// `CLASS ... IMPLEMENTATION ... ENDCLASS.` block.
+    ClassImplementation,
+    /// `INTERFACE ... ENDINTERFACE.` block.
+    InterfaceDefinition,
+    /// `METHOD ... ENDMETHOD.` block.
+    MethodImplementation,
+
+    /// Inline declaration `DATA(name)` or `FINAL(name)` inside a statement.
+    InlineDeclaration,
+    /// A clause of an Open SQL statement, such as `FROM` or `WHERE`.
+    SqlClause,
+    /// Code in another language, such as the body of `EXEC SQL ... ENDEXEC`.
+    EmbeddedCode,
+
+    /// Tokens that could not be parsed into a valid construct.
+    Error,
+}
+
+/// Kind of trivia attached to a token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    /// Spaces, tabs and line breaks.
+    Whitespace,
+    /// A line comment.
+    Comment,
+    /// An ABAP Doc comment line starting with `"!`.
+    DocComment,
+    /// A pragma such as `##NEEDED`.
+    Pragma,
+}
+
+/// Source text without syntactic meaning, kept to make the tree lossless.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia {
+    /// The kind of trivia.
+    pub kind: TriviaKind,
+    /// The exact source text.
+    pub text: String,
+}
+
+/// A token in the syntax tree together with its leading trivia.
+#[derive(Debug, Clone)]
+pub struct SyntaxToken {
+    /// The token as produced by the tokenizer.
+    pub token: Token,
+    /// The exact source text of the token.
+    pub text: String,
+    /// Whitespace and comments preceding the token.
+    pub leading_trivia: Vec<Trivia>,
+}
+
+/// A child of a syntax node: either another node or a token.
+///
+/// Most children are tokens, so boxing them to shrink the enum would only
+/// add an allocation per token.
+#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum SyntaxElement {
+    /// A nested node.
+    Node(SyntaxNode),
+    /// A token.
+    Token(SyntaxToken),
+}
+
+/// An interior node of the syntax tree.
+#[derive(Debug, Clone)]
+pub struct SyntaxNode {
+    /// The kind of the node.
+    pub kind: SyntaxKind,
+    /// The children of the node, in source order.
+    pub children: Vec<SyntaxElement>,
+}
+
+/// The result of parsing a source file.
+#[derive(Debug, Clone)]
+pub struct SyntaxTree {
+    /// The root node, always of kind `SourceFile`.
+    pub root: SyntaxNode,
+    /// Trivia after the last token of the file.
+    pub trailing_trivia: Vec<Trivia>,
+    /// Problems found while tokenizing and parsing.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl SyntaxToken {
+    /// Returns the token value in upper case, as used for keyword comparison.
+    ///
+    /// For keyword phrases this is the normalized phrase (`ORDER BY`), without
+    /// the whitespace or comments found between its words in the source.
+    pub fn upper(&self) -> String {
+        self.token.value.to_uppercase()
+    }
+
+    /// Returns the token text if the token is a symbol such as `.` or `(`.
+    ///
+    /// See `Token::symbol`.
+    pub fn symbol(&self) -> &str {
+        self.token.symbol()
+    }
+}
+
+impl SyntaxNode {
+    /// Creates a new SyntaxNode.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the node
+    /// * `children` - The children of the node, in source order
+    pub fn new(kind: SyntaxKind, children: Vec<SyntaxElement>) -> Self {
+        SyntaxNode { kind, children }
+    }
+
+    /// Returns an iterator over the direct child nodes.
+    pub fn child_nodes(&self) -> impl Iterator<Item = &SyntaxNode> {
+        self.children.iter().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// Returns all tokens below this node, in source order.
+    pub fn tokens(&self) -> Vec<&SyntaxToken> {
+        let mut tokens = Vec::new();
+        self.collect_tokens(&mut tokens);
+        tokens
+    }
+
+    /// Returns the first token below this node, if any.
+    pub fn first_token(&self) -> Option<&SyntaxToken> {
+        self.children.iter().find_map(|child| match child {
+            SyntaxElement::Node(node) => node.first_token(),
+            SyntaxElement::Token(token) => Some(token),
+        })
+    }
+
+    /// Returns all descendant nodes of the given kind, in source order.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of nodes to look for
+    pub fn descendants(&self, kind: SyntaxKind) -> Vec<&SyntaxNode> {
+        let mut nodes = Vec::new();
+        for child in self.child_nodes() {
+            if child.kind == kind {
+                nodes.push(child);
+            }
+            nodes.extend(child.descendants(kind));
+        }
+        nodes
+    }
+
+    fn collect_tokens<'a>(&'a self, tokens: &mut Vec<&'a SyntaxToken>) {
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => node.collect_tokens(tokens),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+    }
+}
+
+impl fmt::Display for SyntaxToken {
+    /// Writes the leading trivia followed by the token text.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for trivia in &self.leading_trivia {
+            f.write_str(&trivia.text)?;
+        }
+        f.write_str(&self.text)
+    }
+}
+
+impl fmt::Display for SyntaxNode {
+    /// Writes the exact source text covered by the node.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for child in &self.children {
+            match child {
+                SyntaxElement::Node(node) => write!(f, "{}", node)?,
+                SyntaxElement::Token(token) => write!(f, "{}", token)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for SyntaxTree {
+    /// Writes the complete source text the tree was parsed from.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.root)?;
+        for trivia in &self.trailing_trivia {
+            f.write_str(&trivia.text)?;
+        }
+        Ok(())
+    }
+}
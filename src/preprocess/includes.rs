@@ -0,0 +1,267 @@
+// abap-tokenizer/src/preprocess/includes.rs
+//! Resolution of `INCLUDE` statements into one logical token stream.
+//!
+//! Programs and function groups are split across include programs, inserted
+//! with `INCLUDE zfoo_top.`. The resolver looks the includes up below a
+//! source root, tokenizes them and inlines their tokens in place of the
+//! `INCLUDE` statement. Every token records the file it comes from in
+//! `file_id`, and its `span` stays relative to that file; the `SourceMap`
+//! maps file ids back to paths and source texts.
+//!
+//! Include files are found by program name, in plain layouts (`zfoo_top.abap`)
+//! as well as in abapGit layouts (`zfoo_top.prog.abap`, or
+//! `zfoo.fugr.lzfootop.abap` for function group includes).
+use crate::abapgit::filename::{unescape_name, AbapGitFile};
+use crate::config::TokenizerConfig;
+use crate::diagnostic::Diagnostic;
+use crate::encoding::{read_source, SourceText};
+use crate::error::TokenizerError;
+use crate::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use crate::tokenizer::token::Token;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Keyword of the statement inserting an include program.
+const INCLUDE: &str = "INCLUDE";
+/// Words that make `INCLUDE` part of a structure definition instead.
+const STRUCTURE_INCLUDE_WORDS: &[&str] = &["TYPE", "STRUCTURE"];
+/// Extension of ABAP source files.
+const SOURCE_EXTENSION: &str = "abap";
+
+/// A source file taking part in a resolved program.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    /// The program name, in upper case, such as `ZFOO_TOP`.
+    pub name: String,
+    /// The path of the file.
+    pub path: PathBuf,
+    /// The source text of the file. Token spans are offsets in its decoded
+    /// text; `SourceText::original_span` converts them to the original file.
+    pub source: SourceText,
+}
+
+/// Maps the file ids of tokens to their source files.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// The files, indexed by file id. The main program has id 0.
+    pub files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    /// Returns the file with the given id.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_id` - The file id of a token
+    pub fn file(&self, file_id: usize) -> Option<&SourceFile> {
+        self.files.get(file_id)
+    }
+
+    /// Returns the source text of a token in its own file.
+    ///
+    /// # Arguments
+    ///
+    /// * `token` - A token of the resolved program
+    pub fn text(&self, token: &Token) -> Option<&str> {
+        self.file(token.file_id)?
+            .source
+            .text
+            .get(token.span.start..token.span.end)
+    }
+
+    /// Adds a file and returns its id.
+    fn add(&mut self, name: String, path: PathBuf, source: SourceText) -> usize {
+        self.files.push(SourceFile { name, path, source });
+        self.files.len() - 1
+    }
+}
+
+/// A program with its includes resolved.
+#[derive(Debug, Clone)]
+pub struct ResolvedProgram {
+    /// The tokens of the program with every include inlined.
+    pub tokens: Vec<Token>,
+    /// The files the tokens come from.
+    pub source_map: SourceMap,
+    /// Missing and recursive includes, tagged with the file they occur in.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Resolves `INCLUDE` statements against the files below a source root.
+pub struct IncludeResolver {
+    /// The configuration used to tokenize every file.
+    config: TokenizerConfig,
+    /// Paths of the source files below the root, by upper-case program name.
+    index: HashMap<String, PathBuf>,
+}
+
+impl IncludeResolver {
+    /// Creates a new IncludeResolver, indexing the source files below `root`.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The directory containing the include programs
+    /// * `config` - The TokenizerConfig to use for tokenization
+    ///
+    /// # Returns
+    ///
+    /// * `Result<IncludeResolver, TokenizerError>` - The resolver, or an error
+    ///   if the directory cannot be read.
+    pub fn new(root: &Path, config: TokenizerConfig) -> Result<Self, TokenizerError> {
+        let mut index = HashMap::new();
+        Self::index_directory(root, &mut index)?;
+        Ok(IncludeResolver { config, index })
+    }
+
+    /// Tokenizes a main program and inlines its includes.
+    ///
+    /// # Arguments
+    ///
+    /// * `main` - The path of the main program
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ResolvedProgram, TokenizerError>` - The resolved program, or
+    ///   an error if a file cannot be read or tokenized.
+    pub fn resolve(&self, main: &Path) -> Result<ResolvedProgram, TokenizerError> {
+        let mut program = ResolvedProgram {
+            tokens: Vec::new(),
+            source_map: SourceMap::default(),
+            diagnostics: Vec::new(),
+        };
+        let name = program_name(main).unwrap_or_default();
+        let mut stack = Vec::new();
+        program.tokens = self.load(main, name, &mut stack, &mut program)?;
+        Ok(program)
+    }
+
+    /// Tokenizes a file and, recursively, the includes it uses.
+    fn load(
+        &self,
+        path: &Path,
+        name: String,
+        stack: &mut Vec<String>,
+        program: &mut ResolvedProgram,
+    ) -> Result<Vec<Token>, TokenizerError> {
+        let source = read_source(path, self.config.metadata.encoding)?;
+        let mut tokenizer = FlexibleTokenizer::new(&source.text, self.config.clone());
+        let mut tokens = tokenizer.tokenize()?;
+        let tokenizer_diagnostics = tokenizer.diagnostics().to_vec();
+        let file_id = program
+            .source_map
+            .add(name.clone(), path.to_path_buf(), source);
+        for token in &mut tokens {
+            token.file_id = file_id;
+        }
+        program.diagnostics.extend(
+            tokenizer_diagnostics
+                .into_iter()
+                .map(|diagnostic| diagnostic.in_file(file_id)),
+        );
+
+        stack.push(name);
+        let mut resolved = Vec::with_capacity(tokens.len());
+        let mut index = 0;
+        while index < tokens.len() {
+            let Some((include, optional, end)) = include_statement(&tokens, index) else {
+                resolved.push(tokens[index].clone());
+                index += 1;
+                continue;
+            };
+            let statement = &tokens[index];
+            if stack.contains(&include) {
+                program.diagnostics.push(
+                    Diagnostic::error(
+                        format!("recursive INCLUDE of {}", include),
+                        statement.line,
+                        statement.column,
+                    )
+                    .in_file(file_id),
+                );
+            } else if let Some(include_path) = self.index.get(&include) {
+                resolved.extend(self.load(include_path, include, stack, program)?);
+            } else if !optional {
+                program.diagnostics.push(
+                    Diagnostic::error(
+                        format!("include {} not found", include),
+                        statement.line,
+                        statement.column,
+                    )
+                    .in_file(file_id),
+                );
+            }
+            index = end;
+        }
+        stack.pop();
+        Ok(resolved)
+    }
+
+    /// Adds the source files below a directory to the index.
+    fn index_directory(
+        directory: &Path,
+        index: &mut HashMap<String, PathBuf>,
+    ) -> Result<(), TokenizerError> {
+        let io_error =
+            |e: std::io::Error| TokenizerError::IoError(format!("{}: {}", directory.display(), e));
+        for entry in fs::read_dir(directory).map_err(io_error)? {
+            let path = entry.map_err(io_error)?.path();
+            if path.is_dir() {
+                Self::index_directory(&path, index)?;
+            } else if path.extension().is_some_and(|ext| ext == SOURCE_EXTENSION) {
+                if let Some(name) = program_name(&path) {
+                    index.entry(name).or_insert(path);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the program name of a source file.
+///
+/// abapGit file names give the name of the object, or of the include for the
+/// parts of a function group. Other file names give the name before the first
+/// `.`, in upper case.
+fn program_name(path: &Path) -> Option<String> {
+    if let Some(file) = AbapGitFile::parse(path) {
+        return Some(file.program_name());
+    }
+    let file_name = path.file_name()?.to_str()?;
+    let stem = file_name.split('.').next()?;
+    Some(unescape_name(stem))
+}
+
+/// Recognizes an `INCLUDE name.` or `INCLUDE name IF FOUND.` statement.
+///
+/// # Returns
+///
+/// The upper-case include name, whether the include is optional and the
+/// index just past the statement, or None if no include statement starts at
+/// `start`.
+fn include_statement(tokens: &[Token], start: usize) -> Option<(String, bool, usize)> {
+    let statement_start = start == 0
+        || tokens[..start]
+            .iter()
+            .rev()
+            .find(|token| !token.is_comment())
+            .is_none_or(|token| token.symbol() == ".");
+    if !statement_start || !tokens[start].value.eq_ignore_ascii_case(INCLUDE) {
+        return None;
+    }
+    let end = tokens[start..]
+        .iter()
+        .position(|token| token.symbol() == ".")
+        .map_or(tokens.len(), |offset| start + offset + 1);
+    let words: Vec<String> = tokens[start + 1..end]
+        .iter()
+        .filter(|token| !token.is_comment() && token.symbol() != ".")
+        .map(|token| token.value.to_uppercase())
+        .collect();
+    let name = words.first()?;
+    if STRUCTURE_INCLUDE_WORDS.contains(&name.as_str()) {
+        return None;
+    }
+    let optional = words[1..].join(" ") == "IF FOUND";
+    Some((name.clone(), optional, end))
+}
@@ -0,0 +1,262 @@
+// abap-tokenizer/src/preprocess/macros.rs
+//! Macro definitions and their expansion at call sites.
+//!
+//! A macro is defined with `DEFINE name. ... END-OF-DEFINITION.` and invoked
+//! as a statement starting with its name, such as `add_line lv_a lv_b.` or
+//! chained as `add_line: lv_a lv_b, lv_c lv_d.`. Each invocation is replaced
+//! by the macro body, with the placeholders `&1` to `&9` replaced by the
+//! arguments. Arguments are separated by whitespace; adjacent tokens such as
+//! `ls_data-field` form a single argument.
+use crate::diagnostic::Diagnostic;
+use crate::tokenizer::token::{MacroOrigin, Span, Token};
+use std::collections::HashMap;
+
+/// Keyword starting a macro definition.
+const DEFINE: &str = "DEFINE";
+/// Keyword ending a macro definition.
+const END_OF_DEFINITION: &str = "END-OF-DEFINITION";
+/// Prefix of a macro placeholder such as `&1`.
+const PLACEHOLDER_PREFIX: char = '&';
+/// Maximum nesting of macro invocations inside macro bodies.
+const MAX_EXPANSION_DEPTH: usize = 32;
+
+/// A macro defined with `DEFINE ... END-OF-DEFINITION`.
+#[derive(Debug, Clone)]
+pub struct MacroDefinition {
+    /// The name of the macro, in upper case.
+    pub name: String,
+    /// The tokens of the body, without comments.
+    pub body: Vec<Token>,
+    /// The line number of the `DEFINE` statement.
+    pub line: usize,
+    /// The column number of the `DEFINE` statement.
+    pub column: usize,
+    /// The byte range from `DEFINE` to the period after `END-OF-DEFINITION`.
+    pub span: Span,
+}
+
+/// The result of expanding the macros of a token stream.
+#[derive(Debug, Clone)]
+pub struct MacroExpansion {
+    /// The token stream with macro definitions removed and invocations
+    /// replaced by the expanded bodies.
+    pub tokens: Vec<Token>,
+    /// The macros defined in the source, in source order.
+    pub definitions: Vec<MacroDefinition>,
+    /// Problems found while expanding.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Expands the macros of a token stream.
+///
+/// A macro can be used after its definition only, as in ABAP. Macros invoked
+/// inside macro bodies are expanded as well. Every expanded token records
+/// the invocation it comes from in `macro_origin`.
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens of a complete source, in source order
+///
+/// # Returns
+///
+/// The expanded tokens, the macro definitions and the diagnostics.
+pub fn expand_macros(tokens: Vec<Token>) -> MacroExpansion {
+    let mut expander = Expander {
+        macros: HashMap::new(),
+        definitions: Vec::new(),
+        diagnostics: Vec::new(),
+    };
+    let tokens = expander.expand(tokens, 0);
+    MacroExpansion {
+        tokens,
+        definitions: expander.definitions,
+        diagnostics: expander.diagnostics,
+    }
+}
+
+/// State of a macro expansion.
+struct Expander {
+    /// Macros defined so far, by upper-case name.
+    macros: HashMap<String, usize>,
+    /// All macro definitions, in source order.
+    definitions: Vec<MacroDefinition>,
+    /// Problems found while expanding.
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Expander {
+    /// Expands a token stream, statement by statement.
+    fn expand(&mut self, tokens: Vec<Token>, depth: usize) -> Vec<Token> {
+        let mut output = Vec::with_capacity(tokens.len());
+        let mut index = 0;
+        while index < tokens.len() {
+            let token = &tokens[index];
+            if token.is_comment() {
+                output.push(token.clone());
+                index += 1;
+                continue;
+            }
+            let end = statement_end(&tokens, index);
+            let head = token.value.to_uppercase();
+            if head == DEFINE && depth == 0 {
+                index = self.define(&tokens, index, end);
+                continue;
+            }
+            match self.macros.get(&head) {
+                Some(&definition) => {
+                    output.extend(self.invoke(definition, &tokens[index..end], depth));
+                }
+                None => output.extend(tokens[index..end].iter().cloned()),
+            }
+            index = end;
+        }
+        output
+    }
+
+    /// Records the definition starting at `start` and returns the index just
+    /// past its `END-OF-DEFINITION.` statement.
+    fn define(&mut self, tokens: &[Token], start: usize, header_end: usize) -> usize {
+        let define = &tokens[start];
+        let Some(name) = tokens[start + 1..header_end]
+            .iter()
+            .find(|token| !token.is_comment() && token.symbol() != ".")
+        else {
+            self.diagnostics.push(Diagnostic::error(
+                "DEFINE without a macro name",
+                define.line,
+                define.column,
+            ));
+            return header_end;
+        };
+        let Some(body_end) = (header_end..tokens.len())
+            .find(|&i| tokens[i].value.eq_ignore_ascii_case(END_OF_DEFINITION))
+        else {
+            self.diagnostics.push(Diagnostic::error(
+                format!("missing {} for macro {}", END_OF_DEFINITION, name.value),
+                define.line,
+                define.column,
+            ));
+            return tokens.len();
+        };
+        let end = statement_end(tokens, body_end);
+        let definition = MacroDefinition {
+            name: name.value.to_uppercase(),
+            body: tokens[header_end..body_end]
+                .iter()
+                .filter(|token| !token.is_comment())
+                .cloned()
+                .collect(),
+            line: define.line,
+            column: define.column,
+            span: Span::new(define.span.start, tokens[end - 1].span.end),
+        };
+        self.macros
+            .insert(definition.name.clone(), self.definitions.len());
+        self.definitions.push(definition);
+        end
+    }
+
+    /// Expands an invocation of a macro.
+    ///
+    /// `statement` holds the tokens of the invocation, from the macro name to
+    /// the period. A chained invocation expands the body once per link.
+    fn invoke(&mut self, definition: usize, statement: &[Token], depth: usize) -> Vec<Token> {
+        let call = &statement[0];
+        let name = self.definitions[definition].name.clone();
+        if depth >= MAX_EXPANSION_DEPTH {
+            self.diagnostics.push(Diagnostic::error(
+                format!("macro {} is expanded recursively", name),
+                call.line,
+                call.column,
+            ));
+            return statement.to_vec();
+        }
+        let origin = MacroOrigin {
+            macro_name: name,
+            call_line: call.line,
+            call_column: call.column,
+            call_span: Span::new(
+                call.span.start,
+                statement.last().map_or(call.span.end, |last| last.span.end),
+            ),
+        };
+
+        let significant: Vec<&Token> = statement[1..]
+            .iter()
+            .filter(|token| !token.is_comment() && token.symbol() != ".")
+            .collect();
+        let links: Vec<&[&Token]> = match significant.split_first() {
+            Some((colon, rest)) if colon.symbol() == ":" => {
+                rest.split(|token| token.symbol() == ",").collect()
+            }
+            _ => vec![&significant[..]],
+        };
+
+        let mut expanded = Vec::new();
+        for link in links {
+            let arguments = split_arguments(link);
+            let body = self.substitute(definition, &arguments, call);
+            let mut tokens = self.expand(body, depth + 1);
+            for token in &mut tokens {
+                if token.macro_origin.is_none() {
+                    token.macro_origin = Some(origin.clone());
+                }
+            }
+            expanded.extend(tokens);
+        }
+        expanded
+    }
+
+    /// Returns the body of a macro with its placeholders replaced.
+    fn substitute(&mut self, definition: usize, arguments: &[Vec<Token>], call: &Token) -> Vec<Token> {
+        let mut body = Vec::new();
+        for token in &self.definitions[definition].body {
+            let placeholder = token
+                .value
+                .strip_prefix(PLACEHOLDER_PREFIX)
+                .and_then(|number| number.parse::<usize>().ok())
+                .filter(|number| (1..=9).contains(number));
+            match placeholder {
+                Some(number) => match arguments.get(number - 1) {
+                    Some(argument) => body.extend(argument.iter().cloned()),
+                    None => self.diagnostics.push(Diagnostic::warning(
+                        format!(
+                            "macro {} uses {} but only {} arguments are given",
+                            self.definitions[definition].name,
+                            token.value,
+                            arguments.len()
+                        ),
+                        call.line,
+                        call.column,
+                    )),
+                },
+                None => body.push(token.clone()),
+            }
+        }
+        body
+    }
+}
+
+/// Returns the index just past the statement starting at `start`.
+fn statement_end(tokens: &[Token], start: usize) -> usize {
+    tokens[start..]
+        .iter()
+        .position(|token| token.symbol() == ".")
+        .map_or(tokens.len(), |offset| start + offset + 1)
+}
+
+/// Splits the arguments of an invocation at whitespace.
+fn split_arguments(tokens: &[&Token]) -> Vec<Vec<Token>> {
+    let mut arguments: Vec<Vec<Token>> = Vec::new();
+    for token in tokens {
+        let adjacent = arguments
+            .last()
+            .and_then(|argument| argument.last())
+            .is_some_and(|last| last.span.end == token.span.start);
+        match arguments.last_mut() {
+            Some(argument) if adjacent => argument.push((*token).clone()),
+            _ => arguments.push(vec![(*token).clone()]),
+        }
+    }
+    arguments
+}
@@ -0,0 +1,12 @@
+// abap-tokenizer/src/preprocess/mod.rs
+//! Passes transforming a token stream before it is analysed.
+//!
+//! The tokenizer reproduces the source text faithfully. Some constructs,
+//! such as macros and includes, only make sense once they are resolved; the passes in
+//! this module produce a rewritten token stream for that purpose.
+
+/// Macro definitions and their expansion at call sites.
+pub mod macros;
+
+/// Inlining of `INCLUDE` programs with a multi-file source map.
+pub mod includes;
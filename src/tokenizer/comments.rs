@@ -0,0 +1,70 @@
+// abap-tokenizer/src/tokenizer/comments.rs
+//! Scanning of line and block comments.
+//!
+//! Comments are described by the `[[comments]]` rules of a configuration
+//! (see `CommentRule`). A line comment such as `" text` extends to the end of
+//! the line. A block comment such as `/* text */` extends to its end
+//! delimiter; when the rule is `nested`, every start delimiter inside the
+//! comment opens a level that needs its own end delimiter, so that
+//! `/* a /* b */ c */` is a single comment.
+use crate::config::tokenizer_config::CommentRule;
+
+/// A comment found at the start of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommentMatch {
+    /// Length of the comment in bytes, including its delimiters.
+    pub length: usize,
+    /// Whether a block comment was closed. An unterminated block comment
+    /// extends to the end of the input.
+    pub terminated: bool,
+}
+
+/// Scans a comment at the start of the input.
+///
+/// The `start_column` of the rule is not checked here, since it depends on
+/// the position of the input in its line.
+///
+/// # Arguments
+///
+/// * `input` - The input starting at the candidate position
+/// * `rule` - The comment rule to match
+///
+/// # Returns
+///
+/// The comment found, or None if the input does not start with the start
+/// delimiter of the rule.
+pub fn scan(input: &str, rule: &CommentRule) -> Option<CommentMatch> {
+    if !input.starts_with(&rule.start) {
+        return None;
+    }
+    let Some(end) = &rule.end else {
+        let length = input.find('\n').map_or(input.len(), |index| index + 1);
+        return Some(CommentMatch { length, terminated: true });
+    };
+
+    let mut depth = 1;
+    let mut position = rule.start.len();
+    while let Some(end_offset) = input[position..].find(end.as_str()) {
+        let nested_start = rule
+            .nested
+            .then(|| input[position..position + end_offset].find(rule.start.as_str()))
+            .flatten();
+        match nested_start {
+            Some(start_offset) => {
+                depth += 1;
+                position += start_offset + rule.start.len();
+            }
+            None => {
+                depth -= 1;
+                position += end_offset + end.len();
+                if depth == 0 {
+                    return Some(CommentMatch { length: position, terminated: true });
+                }
+            }
+        }
+    }
+    Some(CommentMatch {
+        length: input.len(),
+        terminated: false,
+    })
+}
@@ -0,0 +1,219 @@
+// abap-tokenizer/src/tokenizer/contextual_classifier.rs
+//! Contextual classification of keywords used as identifiers.
+//!
+//! ABAP lets most keywords be used as names (`DATA type TYPE i.`,
+//! `ls_data-value`, `lo_obj->do( )`). The regular patterns cannot see the
+//! surrounding tokens, so they tag such names as `Keyword`. This module runs
+//! after tokenization and reclassifies those tokens as `Identifier` according
+//! to the `contextual_rules` of the configuration.
+use super::token::Token;
+use super::token_type::TokenType;
+use crate::config::tokenizer_config::{ContextualRule, MAIN_MODE};
+use log::debug;
+
+/// Position of a significant token within its statement.
+struct TokenContext {
+    /// Index of the token in the token list.
+    index: usize,
+    /// Index of the first significant token of the statement, in the context list.
+    statement_start: usize,
+    /// Parenthesis nesting depth at the token.
+    depth: usize,
+}
+
+/// Reclassifies keyword tokens appearing in identifier position.
+pub struct ContextualClassifier<'a> {
+    /// The rules to apply, in configuration order.
+    rules: &'a [ContextualRule],
+}
+
+impl<'a> ContextualClassifier<'a> {
+    /// Creates a new ContextualClassifier instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `rules` - The contextual rules to apply
+    ///
+    /// # Returns
+    ///
+    /// A new ContextualClassifier instance
+    pub fn new(rules: &'a [ContextualRule]) -> Self {
+        ContextualClassifier { rules }
+    }
+
+    /// Applies the rules to a token list in place.
+    ///
+    /// Only tokens of category `Keyword` lexed in the main mode are considered.
+    /// Comments and pragmas are skipped when looking at neighbouring tokens.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokens` - The tokens of a complete source, in source order
+    pub fn apply(&self, tokens: &mut [Token]) {
+        if self.rules.is_empty() {
+            return;
+        }
+        let contexts = Self::significant_tokens(tokens);
+        let mut reclassified = vec![false; contexts.len()];
+
+        // Walk backwards so that a name is reclassified before the token in
+        // front of it is examined: in `DATA type TYPE i.` the name `type` must
+        // not make `DATA` look like it is followed by the keyword `TYPE`.
+        for position in (0..contexts.len()).rev() {
+            let index = contexts[position].index;
+            if tokens[index].token_type.category != "Keyword" || tokens[index].mode != MAIN_MODE {
+                continue;
+            }
+            let rule = self
+                .rules
+                .iter()
+                .find(|rule| Self::matches(rule, tokens, &contexts, &reclassified, position));
+            if let Some(rule) = rule {
+                let token = &mut tokens[index];
+                debug!(
+                    "Rule {} reclassifies {} at {}:{} as identifier",
+                    rule.name, token.value, token.line, token.column
+                );
+                token.token_type =
+                    TokenType::new("Identifier".to_string(), rule.subcategory.clone());
+                reclassified[position] = true;
+            }
+        }
+    }
+
+    /// Collects the tokens other than comments and pragmas with their
+    /// statement and nesting context.
+    fn significant_tokens(tokens: &[Token]) -> Vec<TokenContext> {
+        let mut contexts = Vec::new();
+        let mut statement_start = 0;
+        let mut depth = 0usize;
+
+        for (index, token) in tokens.iter().enumerate() {
+            if token.is_comment() || token.token_type.category == "Pragma" {
+                continue;
+            }
+            if token.symbol() == ")" {
+                depth = depth.saturating_sub(1);
+            }
+            contexts.push(TokenContext {
+                index,
+                statement_start,
+                depth,
+            });
+            match token.symbol() {
+                "(" => depth += 1,
+                "." => {
+                    statement_start = contexts.len();
+                    depth = 0;
+                }
+                _ => {}
+            }
+        }
+        contexts
+    }
+
+    /// Checks whether a rule applies to the significant token at `position`.
+    fn matches(
+        rule: &ContextualRule,
+        tokens: &[Token],
+        contexts: &[TokenContext],
+        reclassified: &[bool],
+        position: usize,
+    ) -> bool {
+        let context = &contexts[position];
+        let words_at = |pos: usize| -> Vec<String> {
+            tokens[contexts[pos].index]
+                .value
+                .split_whitespace()
+                .map(str::to_uppercase)
+                .collect()
+        };
+
+        if let Some(after) = &rule.after {
+            // Words of the preceding tokens in the statement, nearest first.
+            // Keyword phrases contribute each of their words.
+            let mut previous: Vec<String> = Vec::new();
+            let longest = after
+                .iter()
+                .map(|entry| entry.split_whitespace().count())
+                .max();
+            let mut pos = position;
+            while pos > context.statement_start && previous.len() < longest.unwrap_or(0) {
+                pos -= 1;
+                previous.extend(words_at(pos).into_iter().rev());
+            }
+            let preceded = after.iter().any(|entry| {
+                let words: Vec<String> = entry.split_whitespace().map(str::to_uppercase).collect();
+                words.len() <= previous.len()
+                    && words
+                        .iter()
+                        .rev()
+                        .zip(&previous)
+                        .all(|(word, prev)| word == prev)
+            });
+            if !preceded {
+                return false;
+            }
+        }
+
+        if rule.adjacent == Some(true) {
+            let glued = position > context.statement_start
+                && tokens[contexts[position - 1].index].span.end
+                    == tokens[context.index].span.start;
+            if !glued {
+                return false;
+            }
+        }
+
+        if let Some(before) = &rule.before {
+            let followed = position + 1 < contexts.len()
+                && !reclassified[position + 1]
+                && words_at(position + 1)
+                    .first()
+                    .is_some_and(|next| before.iter().any(|word| *next == word.to_uppercase()));
+            if !followed {
+                return false;
+            }
+        }
+
+        if let Some(starts) = &rule.statement_starts {
+            let first = Self::statement_word(tokens, contexts, context.statement_start);
+            if !starts.iter().any(|word| word.to_uppercase() == first) {
+                return false;
+            }
+        }
+
+        if let Some(in_parentheses) = rule.in_parentheses {
+            if (context.depth > 0) != in_parentheses {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Returns the upper-case first word of the statement starting at `start`.
+    ///
+    /// Hyphenated keywords split into several tokens, such as `CLASS-DATA`,
+    /// are joined back together.
+    fn statement_word(tokens: &[Token], contexts: &[TokenContext], start: usize) -> String {
+        let mut word = String::new();
+        let mut position = start;
+        while let Some(context) = contexts.get(position) {
+            let token = &tokens[context.index];
+            if position > start {
+                let previous = &tokens[contexts[position - 1].index];
+                if previous.span.end != token.span.start {
+                    break;
+                }
+            }
+            let part = token.value.to_uppercase();
+            if position > start && part != "-" && !word.ends_with('-') {
+                break;
+            }
+            word.push_str(&part);
+            position += 1;
+        }
+        word
+    }
+}
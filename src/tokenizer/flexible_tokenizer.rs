@@ -5,11 +5,18 @@
 //! based on a provided configuration. It handles various token types, special rules,
 //! and provides detailed error reporting and debugging information.
 
-use std::collections::HashMap;
-use super::token::Token;
+use std::collections::{HashMap, VecDeque};
+use super::comments;
+use super::contextual_classifier::ContextualClassifier;
+use super::literals;
+use super::string_template::{self, OpenTemplate, TemplateState};
+use super::token::{visual_column, Span, Token};
 use super::token_type::TokenType;
-use crate::config::tokenizer_config::SpecialRule;
+use crate::config::tokenizer_config::{
+    CategoryConfig, CommentRule, CompiledPatternConfig, Phrase, PhraseEmit, SpecialRule, MAIN_MODE,
+};
 use crate::config::TokenizerConfig;
+use crate::diagnostic::Diagnostic;
 use crate::error::TokenizerError;
 use log::debug;
 use regex::Regex;
@@ -18,6 +25,55 @@ use regex::Regex;
 
 /// Type alias for a function that validates special rules.
 type RuleValidator = Box<dyn Fn(&str, &SpecialRule, usize) -> Option<usize>>;
+
+/// Change of lexer mode requested by a matched pattern or special rule.
+enum ModeTransition {
+    /// Remain in the active mode.
+    Stay,
+    /// Enter the named mode.
+    Push(String),
+    /// Enter the named mode once a token with the given value is produced.
+    PushAfter(String, String),
+    /// Return to the previous mode.
+    Pop,
+}
+
+impl ModeTransition {
+    /// Builds the transition from the `push`, `push_after` and `pop` attributes of a rule.
+    fn from_rule(push: Option<&String>, push_after: Option<&String>, pop: bool) -> Self {
+        match (push, push_after) {
+            (Some(mode), Some(after)) => ModeTransition::PushAfter(mode.clone(), after.clone()),
+            (Some(mode), None) => ModeTransition::Push(mode.clone()),
+            (None, _) if pop => ModeTransition::Pop,
+            (None, _) => ModeTransition::Stay,
+        }
+    }
+}
+
+/// Rule set of the active lexer mode.
+struct ActiveRules<'c> {
+    /// Token categories and their priorities.
+    token_categories: &'c HashMap<String, CategoryConfig>,
+    /// Compiled patterns for each category.
+    patterns: &'c HashMap<String, Vec<CompiledPatternConfig>>,
+    /// Special rules, in configuration order.
+    special_rules: &'c [SpecialRule],
+    /// Comment rules, in configuration order.
+    comments: &'c [CommentRule],
+}
+
+/// A word of a phrase matched in `members` mode, waiting to be emitted.
+struct PendingMember {
+    /// Byte offset of the word in the input.
+    start: usize,
+    /// Byte offset just past the word.
+    end: usize,
+    /// Type of the token to emit.
+    token_type: TokenType,
+    /// Identifier of the phrase the word belongs to.
+    phrase_id: usize,
+}
+
 pub struct FlexibleTokenizer<'a> {
     /// The input ABAP code to be tokenized.
     input: &'a str,
@@ -29,6 +85,31 @@ pub struct FlexibleTokenizer<'a> {
     line: usize,
     /// The current column number being processed.
     column: usize,
+    /// Byte offset of the start of the current line.
+    line_start: usize,
+    /// Phrase words already matched that are still to be emitted.
+    pending_members: VecDeque<PendingMember>,
+    /// Identifier for the next matched phrase.
+    next_phrase_id: usize,
+    /// Character delimiting string templates, if templates are configured.
+    template_delimiter: Option<char>,
+    /// Escape character of the literal parts of string templates.
+    template_escape: Option<char>,
+    /// Whether the literal parts of string templates may span lines.
+    template_multiline: bool,
+    /// String templates currently open, innermost last.
+    template_stack: Vec<OpenTemplate>,
+    /// Lexer modes entered through `push` transitions, innermost last.
+    mode_stack: Vec<String>,
+    /// Mode to enter, and the token value to wait for, from a `push_after` rule.
+    pending_push: Option<(String, String)>,
+    /// Problems found in the input that did not stop tokenization.
+    diagnostics: Vec<Diagnostic>,
+    /// Words of the preceding significant tokens, nearest last, for the
+    /// `after` conditions of patterns and special rules.
+    previous_words: Vec<String>,
+    /// Number of preceding words the `after` conditions can look at.
+    lookbehind_length: usize,
 }
 
 /// The main tokenizer struct for processing ABAP code.
@@ -44,68 +125,229 @@ impl<'a> FlexibleTokenizer<'a> {
     ///
     /// A new FlexibleTokenizer instance
     pub fn new(input: &'a str, config: TokenizerConfig) -> Self {
+        let template_rule = config.get_context_rule("TemplateString");
+        let template_delimiter = template_rule.and_then(|rule| rule.start.chars().next());
+        let template_escape = template_rule
+            .and_then(|rule| rule.escape.as_deref())
+            .and_then(|escape| escape.chars().next());
+        let template_multiline = template_rule.and_then(|rule| rule.multiline).unwrap_or(false);
+        let lookbehind_length = config.lookbehind_length();
         FlexibleTokenizer {
             input,
             config,
             position: 0,
             line: 1,
             column: 1,
+            line_start: 0,
+            pending_members: VecDeque::new(),
+            next_phrase_id: 0,
+            template_delimiter,
+            template_escape,
+            template_multiline,
+            template_stack: Vec::new(),
+            mode_stack: Vec::new(),
+            pending_push: None,
+            diagnostics: Vec::new(),
+            previous_words: Vec::new(),
+            lookbehind_length,
         }
     }
 
+    /// Returns the problems found in the input so far, such as unterminated
+    /// string templates and block comments.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
     /// Processes the next token in the input.
     ///
     /// This method is the core of the tokenization process. It performs the following steps:
-    /// 1. Skips any whitespace.
-    /// 2. Checks for the end of input.
-    /// 3. Attempts to match special rules.
-    /// 4. Attempts to match regular token patterns.
-    /// 5. Handles unknown tokens if no match is found.
+    /// 1. Scans literal parts when inside a string template.
+    /// 2. Skips any whitespace.
+    /// 3. Checks for the end of input.
+    /// 4. Attempts to match string template delimiters.
+    /// 5. Attempts to match keyword phrases.
+    /// 6. Attempts to match comment rules.
+    /// 7. Attempts to match special rules.
+    /// 8. Attempts to match regular token patterns.
+    /// 9. Handles unknown tokens if no match is found.
+    ///
+    /// Steps 1, 4 and 5 only apply in the main lexer mode; in other modes only
+    /// the rules of the active mode are used. The returned token records the
+    /// mode it was lexed in, and a `push` or `pop` on the matched rule takes
+    /// effect after the token. A mode with an `end` regex is left as soon as
+    /// the regex matches at the start of a token, before lexing it, and a mode
+    /// without rules of its own produces a single region token up to `end`.
+    /// Literal tokens carry their `decoded_value`, and every token its
+    /// `visual_column`.
     ///
     /// # Returns
     ///
     /// * `Result<Option<Token>, TokenizerError>` - The next token if available, None if end of input,
     ///   or an error if tokenization fails.
     pub fn next_token(&mut self) -> Result<Option<Token>, TokenizerError> {
+        self.check_mode_end();
+        let mode = self.current_mode().to_string();
+        let token = self.lex_token()?;
+        if let Some(token) = &token {
+            self.check_pending_push(token);
+            self.remember_words(token);
+        }
+        Ok(token.map(|mut token| {
+            token.mode = mode;
+            token.visual_column = self.visual_column_at(token.span.start);
+            token.decoded_value = literals::decode(&token);
+            token
+        }))
+    }
+
+    /// Records the words of a significant token for the `after` conditions.
+    ///
+    /// Comments and pragmas are skipped, so that they do not separate a
+    /// keyword from the name following it.
+    fn remember_words(&mut self, token: &Token) {
+        if self.lookbehind_length == 0 || token.is_comment() || token.token_type.category == "Pragma"
+        {
+            return;
+        }
+        self.previous_words
+            .extend(token.value.split_whitespace().map(str::to_string));
+        let excess = self.previous_words.len().saturating_sub(self.lookbehind_length);
+        self.previous_words.drain(..excess);
+    }
+
+    /// Returns true if an optional `after` condition holds at the current position.
+    fn is_preceded_by(&self, after: Option<&Vec<String>>) -> bool {
+        after.is_none_or(|after| self.config.is_preceded_by(after, &self.previous_words))
+    }
+
+    /// Returns the visual column of a byte offset in the input.
+    ///
+    /// Offsets on the current line, the common case, use the cached start of
+    /// the line; others, such as the start of a token ending with a line
+    /// break, search back for their line start.
+    fn visual_column_at(&self, offset: usize) -> usize {
+        let line_start = if (self.line_start..=self.position).contains(&offset) {
+            self.line_start
+        } else {
+            self.input[..offset].rfind('\n').map_or(0, |index| index + 1)
+        };
+        visual_column(&self.input[line_start..offset], self.config.metadata.tab_width)
+    }
+
+    /// Returns the name of the active lexer mode.
+    pub fn current_mode(&self) -> &str {
+        self.mode_stack.last().map_or(MAIN_MODE, String::as_str)
+    }
+
+    /// Lexes the next token in the active mode. See `next_token`.
+    fn lex_token(&mut self) -> Result<Option<Token>, TokenizerError> {
+        let in_main_mode = self.current_mode() == MAIN_MODE;
+
+        // Whitespace is significant in the literal parts of string templates
+        if in_main_mode && self.template_state() == Some(TemplateState::Literal) {
+            if let Some(token) = self.next_template_literal_token() {
+                return Ok(Some(token));
+            }
+        }
+
         self.skip_whitespace();
 
         if self.position >= self.input.len() {
+            while !self.template_stack.is_empty() {
+                self.close_unterminated_template();
+            }
             return Ok(None);
         }
 
+        let start = self.position;
+
+        if let Some(token) = self.next_region_token() {
+            return Ok(Some(token));
+        }
+
+        if in_main_mode {
+            // Check string template delimiters
+            if let Some(token) = self.check_template_delimiters() {
+                return Ok(Some(token));
+            }
+
+            // Emit the next word of a phrase matched in members mode
+            if let Some(token) = self.next_pending_member() {
+                return Ok(Some(token));
+            }
+
+            // Check keyword phrases
+            if let Some(token) = self.check_phrases() {
+                return Ok(Some(token));
+            }
+        }
+
+        // Check comments
+        if let Some(token) = self.check_comments() {
+            return Ok(Some(token));
+        }
+
         let remaining_input = &self.input[self.position..];
 
         // Check special rules first
-        if let Some((token, length)) = self.check_special_rules(remaining_input)? {
-            for _ in 0..length {
-                self.advance();
-            }
+        if let Some((mut token, length, transition)) = self.check_special_rules(remaining_input)? {
+            self.advance_bytes(length);
+            token.span = Span::new(start, self.position);
+            self.apply_transition(transition);
             return Ok(Some(token));
         }
 
         // Then check regular patterns
-        if let Some((token, length)) = self.find_next_token(remaining_input)? {
-            for _ in 0..length {
-                self.advance();
-            }
+        if let Some((mut token, length, transition)) = self.find_next_token(remaining_input)? {
+            self.advance_bytes(length);
+            token.span = Span::new(start, self.position);
+            self.apply_transition(transition);
             return Ok(Some(token));
         }
 
         // If no pattern matches, advance one character and return an unknown token
+        let (line, column) = (self.line, self.column);
         let ch = self.advance();
         debug!("Unknown token encountered: {}", ch);
-        Ok(Some(Token::new(
+        let mut token = Token::new(
             TokenType::new("Unknown".to_string(), None),
             ch.to_string(),
-            self.line,
-            self.column,
-        )))
+            line,
+            column,
+        );
+        token.span = Span::new(start, self.position);
+        Ok(Some(token))
+    }
+
+    /// Tokenizes the remaining input and collects all tokens.
+    ///
+    /// Once the whole input is tokenized, the configured contextual rules are
+    /// applied, reclassifying keywords used as identifiers.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Token>, TokenizerError>` - All tokens up to the end of input,
+    ///   or the first error encountered.
+    pub fn tokenize(&mut self) -> Result<Vec<Token>, TokenizerError> {
+        let mut tokens = Vec::new();
+        while let Some(token) = self.next_token()? {
+            tokens.push(token);
+        }
+        ContextualClassifier::new(&self.config.contextual_rules).apply(&mut tokens);
+        Ok(tokens)
     }
     
     /// Attempts to find the next token based on the configured patterns.
     ///
     /// This method iterates through all token categories in order of priority,
     /// attempting to match the input against each pattern in the category.
+    /// A pattern with `followed_by` only matches if the input following the
+    /// match satisfies that regex, as a lookahead would, and an `attached`
+    /// pattern only matches without whitespace on either side, which tells a
+    /// component selector `ls_struct-comp` from the minus in `a - b`. A
+    /// pattern with `after` only matches right after one of the listed token
+    /// sequences, which tells the type name in `TYPE ty_row` from other names.
     ///
     /// # Arguments
     ///
@@ -113,17 +355,28 @@ impl<'a> FlexibleTokenizer<'a> {
     ///
     /// # Returns
     ///
-    /// * `Result<Option<(Token, usize)>, TokenizerError>` - A tuple containing the matched token
-    ///   and its length if found, or None if no match is found.
-    fn find_next_token(&self, input: &str) -> Result<Option<(Token, usize)>, TokenizerError> {
-        let mut categories: Vec<_> = self.config.token_categories.iter().collect();
+    /// * `Result<Option<(Token, usize, ModeTransition)>, TokenizerError>` - A tuple containing
+    ///   the matched token, its length and the mode transition of the pattern if found,
+    ///   or None if no match is found.
+    fn find_next_token(
+        &self,
+        input: &str,
+    ) -> Result<Option<(Token, usize, ModeTransition)>, TokenizerError> {
+        let rules = self.active_rules();
+        let mut categories: Vec<_> = rules.token_categories.iter().collect();
         categories.sort_by_key(|&(_, config)| config.priority);
 
         for (category, _) in categories {
-            if let Some(patterns) = self.config.get_pattern(category) {
+            if let Some(patterns) = rules.patterns.get(category) {
                 for pattern in patterns {
                     if let Some(mat) = pattern.regex.find(input) {
-                        if mat.start() == 0 {
+                        let followed = pattern
+                            .followed_by
+                            .as_ref()
+                            .is_none_or(|next| next.is_match(&input[mat.end()..]));
+                        let attached = !pattern.attached || self.is_attached(&input[mat.end()..]);
+                        let preceded = self.is_preceded_by(pattern.after.as_ref());
+                        if mat.start() == 0 && followed && attached && preceded {
                             let value = mat.as_str().to_string();
                             let token_type = TokenType::new(
                                 category.to_string(),
@@ -131,7 +384,12 @@ impl<'a> FlexibleTokenizer<'a> {
                             );
                             let token = Token::new(token_type, value.clone(), self.line, self.column);
                             debug!("Matched token: {:?}", token);
-                            return Ok(Some((token, mat.end())));
+                            let transition = ModeTransition::from_rule(
+                                pattern.push.as_ref(),
+                                pattern.push_after.as_ref(),
+                                pattern.pop,
+                            );
+                            return Ok(Some((token, mat.end(), transition)));
                         }
                     }
                 }
@@ -141,6 +399,406 @@ impl<'a> FlexibleTokenizer<'a> {
         Ok(None)
     }
 
+    /// Returns true if neither the text before the current position nor the
+    /// text `following` a candidate token starts with whitespace.
+    fn is_attached(&self, following: &str) -> bool {
+        let touches = |ch: Option<char>| ch.is_some_and(|ch| !ch.is_whitespace());
+        touches(self.input[..self.position].chars().next_back()) && touches(following.chars().next())
+    }
+
+    /// Returns the categories, patterns, special rules and comment rules of
+    /// the active mode.
+    fn active_rules(&self) -> ActiveRules<'_> {
+        match self
+            .mode_stack
+            .last()
+            .and_then(|name| self.config.get_mode(name))
+        {
+            Some(mode) => ActiveRules {
+                token_categories: &mode.token_categories,
+                patterns: &mode.patterns,
+                special_rules: &mode.special_rules,
+                comments: &mode.comments,
+            },
+            None => ActiveRules {
+                token_categories: &self.config.token_categories,
+                patterns: &self.config.patterns,
+                special_rules: &self.config.special_rules,
+                comments: &self.config.comments,
+            },
+        }
+    }
+
+    /// Enters or leaves a lexer mode after a token has been produced.
+    fn apply_transition(&mut self, transition: ModeTransition) {
+        match transition {
+            ModeTransition::Push(mode) => {
+                debug!("Entering lexer mode {} at {}:{}", mode, self.line, self.column);
+                self.mode_stack.push(mode);
+            }
+            ModeTransition::PushAfter(mode, after) => {
+                debug!("Entering lexer mode {} after the next {}", mode, after);
+                self.pending_push = Some((mode, after));
+            }
+            ModeTransition::Pop => {
+                debug!("Leaving lexer mode {} at {}:{}", self.current_mode(), self.line, self.column);
+                self.mode_stack.pop();
+            }
+            ModeTransition::Stay => {}
+        }
+    }
+
+    /// Enters the mode of a pending `push_after` once its token is produced.
+    fn check_pending_push(&mut self, token: &Token) {
+        let reached = self
+            .pending_push
+            .as_ref()
+            .is_some_and(|(_, after)| token.value.eq_ignore_ascii_case(after));
+        if reached {
+            if let Some((mode, _)) = self.pending_push.take() {
+                self.apply_transition(ModeTransition::Push(mode));
+            }
+        }
+    }
+
+    /// Leaves every active mode whose `end` regex matches at the next token.
+    ///
+    /// The text matched by `end` is not consumed, so it is lexed by the mode
+    /// that is active afterwards.
+    fn check_mode_end(&mut self) {
+        while let Some(mode) = self.mode_stack.last().and_then(|name| self.config.get_mode(name)) {
+            let Some(end) = &mode.end_at_start else {
+                return;
+            };
+            if !end.is_match(self.input[self.position..].trim_start()) {
+                return;
+            }
+            self.skip_whitespace();
+            self.apply_transition(ModeTransition::Pop);
+        }
+    }
+
+    /// Produces a single token covering the body of a mode without rules.
+    ///
+    /// The token extends up to the first match of the mode's `end` regex, or
+    /// up to the end of input, without trailing whitespace. Its category is
+    /// the mode's `region`, or the mode name if no region is configured.
+    fn next_region_token(&mut self) -> Option<Token> {
+        let name = self.mode_stack.last()?;
+        let mode = self.config.get_mode(name)?;
+        if !mode.is_opaque() {
+            return None;
+        }
+        let category = mode.region.clone().unwrap_or_else(|| name.clone());
+        let remaining_input = &self.input[self.position..];
+        let body_end = mode
+            .end
+            .as_ref()
+            .and_then(|end| end.find(remaining_input))
+            .map_or(remaining_input.len(), |mat| mat.start());
+        let length = remaining_input[..body_end].trim_end().len();
+        if length == 0 {
+            return None;
+        }
+        Some(self.emit_token(&category, length))
+    }
+
+    /// Produces the next token inside the literal part of a string template.
+    ///
+    /// Returns None, after closing the innermost template and reporting it
+    /// as a diagnostic, when the template is not terminated before the end of
+    /// the input, or of the line unless templates may span lines.
+    fn next_template_literal_token(&mut self) -> Option<Token> {
+        let delimiter = self.template_delimiter?;
+        let remaining_input = &self.input[self.position..];
+        match remaining_input.chars().next() {
+            Some(ch) if ch == delimiter => {
+                self.template_stack.pop();
+                Some(self.emit_token(string_template::TEMPLATE_END, ch.len_utf8()))
+            }
+            Some(string_template::EXPR_OPEN) => {
+                self.set_template_state(TemplateState::Expression);
+                Some(self.emit_token(string_template::TEMPLATE_EXPR_OPEN, 1))
+            }
+            Some('\n') if !self.template_multiline => {
+                self.close_unterminated_template();
+                None
+            }
+            None => {
+                self.close_unterminated_template();
+                None
+            }
+            Some(_) => {
+                let length = string_template::scan_literal(
+                    remaining_input,
+                    delimiter,
+                    self.template_escape,
+                    self.template_multiline,
+                );
+                Some(self.emit_token(string_template::TEMPLATE_LITERAL, length))
+            }
+        }
+    }
+
+    /// Returns the lexing state of the innermost open string template.
+    fn template_state(&self) -> Option<TemplateState> {
+        self.template_stack.last().map(|template| template.state)
+    }
+
+    /// Sets the lexing state of the innermost open string template.
+    fn set_template_state(&mut self, state: TemplateState) {
+        if let Some(template) = self.template_stack.last_mut() {
+            template.state = state;
+        }
+    }
+
+    /// Closes the innermost string template, reporting it as unterminated at
+    /// its opening delimiter.
+    fn close_unterminated_template(&mut self) {
+        if let Some(template) = self.template_stack.pop() {
+            debug!("Unterminated string template at {}:{}", template.line, template.column);
+            self.diagnostics.push(Diagnostic::error(
+                "Unterminated string template",
+                template.line,
+                template.column,
+            ));
+        }
+    }
+
+    /// Checks for a string template starting, or an embedded expression of a
+    /// string template ending, at the current position.
+    fn check_template_delimiters(&mut self) -> Option<Token> {
+        let ch = self.input[self.position..].chars().next()?;
+        if Some(ch) == self.template_delimiter {
+            self.template_stack.push(OpenTemplate {
+                state: TemplateState::Literal,
+                line: self.line,
+                column: self.column,
+            });
+            return Some(self.emit_token(string_template::TEMPLATE_START, ch.len_utf8()));
+        }
+        if ch == string_template::EXPR_CLOSE
+            && self.template_state() == Some(TemplateState::Expression)
+        {
+            self.set_template_state(TemplateState::Literal);
+            return Some(self.emit_token(string_template::TEMPLATE_EXPR_CLOSE, 1));
+        }
+        None
+    }
+
+    /// Creates a token of the given category covering the next `length` bytes
+    /// and advances past it.
+    fn emit_token(&mut self, category: &str, length: usize) -> Token {
+        let start = self.position;
+        let mut token = Token::new(
+            TokenType::new(category.to_string(), None),
+            self.input[start..start + length].to_string(),
+            self.line,
+            self.column,
+        );
+        self.advance_bytes(length);
+        token.span = Span::new(start, self.position);
+        token
+    }
+
+    /// Emits the pending phrase member starting at the current position, if any.
+    fn next_pending_member(&mut self) -> Option<Token> {
+        if self.pending_members.front()?.start != self.position {
+            return None;
+        }
+        let member = self.pending_members.pop_front()?;
+        let mut token = Token::new(
+            member.token_type,
+            self.input[member.start..member.end].to_string(),
+            self.line,
+            self.column,
+        );
+        token.phrase_id = Some(member.phrase_id);
+        self.advance_bytes(member.end - member.start);
+        token.span = Span::new(member.start, member.end);
+        Some(token)
+    }
+
+    /// Attempts to match a keyword phrase at the current position.
+    ///
+    /// In `composite` mode the whole phrase, including any whitespace and
+    /// comments between its words, becomes a single token whose value is the
+    /// words separated by single spaces. In `members` mode the first word is
+    /// returned and the remaining words are queued; comments between them are
+    /// tokenized normally.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Token>` - The token for the phrase, or None if no phrase matches.
+    fn check_phrases(&mut self) -> Option<Token> {
+        let (phrase, words) = self
+            .config
+            .phrases
+            .iter()
+            .find_map(|phrase| self.match_phrase(phrase).map(|words| (phrase, words)))?;
+        let token_type = TokenType::new(phrase.category.clone(), phrase.subcategory.clone());
+        let emit = phrase.emit;
+        let phrase_id = self.next_phrase_id;
+        self.next_phrase_id += 1;
+        debug!("Matched phrase {:?} at {}:{}", phrase.words, self.line, self.column);
+
+        let start = self.position;
+        let (value, end) = match emit {
+            PhraseEmit::Composite => {
+                let value = words
+                    .iter()
+                    .map(|&(word_start, word_end)| &self.input[word_start..word_end])
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                (value, words[words.len() - 1].1)
+            }
+            PhraseEmit::Members => {
+                for &(word_start, word_end) in &words[1..] {
+                    self.pending_members.push_back(PendingMember {
+                        start: word_start,
+                        end: word_end,
+                        token_type: token_type.clone(),
+                        phrase_id,
+                    });
+                }
+                (self.input[start..words[0].1].to_string(), words[0].1)
+            }
+        };
+
+        let mut token = Token::new(token_type, value, self.line, self.column);
+        token.phrase_id = Some(phrase_id);
+        self.advance_bytes(end - start);
+        token.span = Span::new(start, end);
+        Some(token)
+    }
+
+    /// Matches the words of a phrase starting at the current position.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Vec<(usize, usize)>>` - The absolute byte range of each word,
+    ///   or None if the phrase does not match.
+    fn match_phrase(&self, phrase: &Phrase) -> Option<Vec<(usize, usize)>> {
+        let previous = self.input[..self.position].chars().next_back();
+        if previous.is_some_and(|ch| Self::is_word_char(ch) || matches!(ch, '~' | '>')) {
+            return None;
+        }
+
+        let mut ranges = Vec::with_capacity(phrase.words.len());
+        let mut offset = self.position;
+        for (index, word) in phrase.words.iter().enumerate() {
+            if index > 0 {
+                let gap_end = self.skip_phrase_gap(offset);
+                if gap_end == offset {
+                    return None;
+                }
+                offset = gap_end;
+            }
+            let candidate = self.input.get(offset..offset + word.len())?;
+            let equal = if self.config.metadata.case_sensitive {
+                candidate == word
+            } else {
+                candidate.eq_ignore_ascii_case(word)
+            };
+            if !equal {
+                return None;
+            }
+            let end = offset + word.len();
+            if self.input[end..].chars().next().is_some_and(Self::is_word_char) {
+                return None;
+            }
+            ranges.push((offset, end));
+            offset = end;
+        }
+        Some(ranges)
+    }
+
+    /// Skips whitespace and comments between the words of a phrase.
+    ///
+    /// Comments are recognised with the comment rules and with the special
+    /// rules whose token type is `Comment`, evaluated at the visual column
+    /// they would start at. Unterminated block comments do not count, so that
+    /// they are reported when lexed.
+    ///
+    /// # Returns
+    ///
+    /// The byte offset of the next word candidate.
+    fn skip_phrase_gap(&self, mut offset: usize) -> usize {
+        loop {
+            let rest = &self.input[offset..];
+            let trimmed = rest.trim_start();
+            offset += rest.len() - trimmed.len();
+            if trimmed.is_empty() {
+                return offset;
+            }
+            let column = self.visual_column_at(offset);
+            if let Some((_, comment)) = self.match_comment(trimmed, column) {
+                if comment.terminated {
+                    offset += comment.length;
+                    continue;
+                }
+                return offset;
+            }
+            match self.match_special_rule(trimmed, column) {
+                Some((rule, length))
+                    if matches!(rule.token_type.as_str(), "Comment" | "DocComment") && length > 0 =>
+                {
+                    offset += length
+                }
+                _ => return offset,
+            }
+        }
+    }
+
+    /// Returns true if a character can be part of an ABAP word.
+    fn is_word_char(ch: char) -> bool {
+        ch.is_alphanumeric() || ch == '_' || ch == '-'
+    }
+
+    /// Checks for a comment at the current position.
+    ///
+    /// An unterminated block comment extends to the end of the input and is
+    /// reported as a diagnostic at its opening position.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Token>` - The comment token, or None if no comment rule matches.
+    fn check_comments(&mut self) -> Option<Token> {
+        let input = &self.input[self.position..];
+        let (rule, comment) = self.match_comment(input, self.visual_column_at(self.position))?;
+        let token_type = rule.token_type.clone();
+        if !comment.terminated {
+            self.diagnostics.push(Diagnostic::error(
+                format!("unterminated block comment starting with {}", rule.start),
+                self.line,
+                self.column,
+            ));
+        }
+        Some(self.emit_token(&token_type, comment.length))
+    }
+
+    /// Finds the first comment rule matching the input at the given column.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input string starting at the candidate position
+    /// * `column` - The visual column the input starts at
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(&CommentRule, CommentMatch)>` - The matching rule and the comment found.
+    fn match_comment(
+        &self,
+        input: &str,
+        column: usize,
+    ) -> Option<(&CommentRule, comments::CommentMatch)> {
+        self.active_rules()
+            .comments
+            .iter()
+            .filter(|rule| rule.start_column.is_none_or(|start_column| start_column == column))
+            .find_map(|rule| comments::scan(input, rule).map(|comment| (rule, comment)))
+    }
+
     /// Checks if any special rules apply to the current input.
     ///
     /// This method iterates through all special rules defined in the configuration,
@@ -152,9 +810,45 @@ impl<'a> FlexibleTokenizer<'a> {
     ///
     /// # Returns
     ///
-    /// * `Result<Option<(Token, usize)>, TokenizerError>` - A tuple containing the matched token
-    ///   and its length if a special rule applies, or None if no special rule matches.
-    fn check_special_rules(&self, input: &str) -> Result<Option<(Token, usize)>, TokenizerError> {
+    /// * `Result<Option<(Token, usize, ModeTransition)>, TokenizerError>` - A tuple containing
+    ///   the matched token, its length and the mode transition of the rule if a special rule
+    ///   applies, or None if no special rule matches.
+    fn check_special_rules(
+        &self,
+        input: &str,
+    ) -> Result<Option<(Token, usize, ModeTransition)>, TokenizerError> {
+        Ok(self
+            .match_special_rule(input, self.visual_column_at(self.position))
+            .map(|(rule, end_pos)| {
+                let token_value = &input[..end_pos];
+                (
+                    Token::new(
+                        TokenType::new(rule.token_type.clone(), None),
+                        token_value.to_string(),
+                        self.line,
+                        self.column,
+                    ),
+                    end_pos,
+                    ModeTransition::from_rule(
+                        rule.push.as_ref(),
+                        rule.push_after.as_ref(),
+                        rule.pop.unwrap_or(false),
+                    ),
+                )
+            }))
+    }
+
+    /// Finds the first special rule matching the input at the given column.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input string starting at the candidate position
+    /// * `column` - The column the input starts at
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(&SpecialRule, usize)>` - The matching rule and the length of its match.
+    fn match_special_rule(&self, input: &str, column: usize) -> Option<(&SpecialRule, usize)> {
         let validators: HashMap<&str, RuleValidator> = vec![
             ("start", Box::new(Self::validate_start) as RuleValidator),
             ("end", Box::new(Self::validate_end) as RuleValidator),
@@ -170,12 +864,15 @@ impl<'a> FlexibleTokenizer<'a> {
         ]
         .into_iter()
         .collect();
-        for rule in &self.config.special_rules {
+        for rule in self.active_rules().special_rules {
+            if !self.is_preceded_by(rule.after.as_ref()) {
+                continue;
+            }
             let mut end_pos = input.len();
             let mut is_valid = true;
             for (attr, validator) in &validators {
-                if let Some(_) = rule.get_attribute(attr) {
-                    match validator(input, rule, self.column) {
+                if rule.get_attribute(attr).is_some() {
+                    match validator(input, rule, column) {
                         Some(pos) => end_pos = end_pos.min(pos),
                         None => {
                             is_valid = false;
@@ -185,19 +882,10 @@ impl<'a> FlexibleTokenizer<'a> {
                 }
             }
             if is_valid {
-                let token_value = &input[..end_pos];
-                return Ok(Some((
-                    Token::new(
-                        TokenType::new(rule.token_type.clone(), None),
-                        token_value.to_string(),
-                        self.line,
-                        self.column,
-                    ),
-                    end_pos,
-                )));
+                return Some((rule, end_pos));
             }
         }
-        Ok(None)
+        None
     }
 
     /// Validates the start condition of a special rule.
@@ -212,7 +900,11 @@ impl<'a> FlexibleTokenizer<'a> {
     /// Validates the end condition of a special rule.
     fn validate_end(input: &str, rule: &SpecialRule, _: usize) -> Option<usize> {
         rule.end.as_ref().and_then(|end| {
-            input[rule.start.len()..]
+            // The validators run in no particular order, so the input may be
+            // shorter than `rule.start` or not start with it at all, and the
+            // offset can fall inside a multi-byte character
+            input
+                .get(rule.start.len()..)?
                 .find(end)
                 .map(|pos| rule.start.len() + pos + end.len())
         })
@@ -272,6 +964,17 @@ impl<'a> FlexibleTokenizer<'a> {
         }
     }
 
+    /// Advances over `length` bytes of input, one character at a time.
+    ///
+    /// Match lengths are byte offsets, so advancing per byte rather than per
+    /// character keeps multi-byte characters from being skipped twice.
+    fn advance_bytes(&mut self, length: usize) {
+        let target = self.position + length;
+        while self.position < target {
+            self.advance();
+        }
+    }
+
     /// Advances the current position in the input by one character.
     ///
     /// This method also updates the line and column counters.
@@ -286,6 +989,7 @@ impl<'a> FlexibleTokenizer<'a> {
             if ch == '\n' {
                 self.line += 1;
                 self.column = 1;
+                self.line_start = self.position;
             }
             ch
         } else {
@@ -0,0 +1,108 @@
+// abap-tokenizer/src/tokenizer/literals.rs
+//! Typed ABAP literals.
+//!
+//! The configuration classifies literals into subcategories: text field
+//! literals `'...'` (`Text`), string literals `` `...` `` (`String`), integers
+//! (`Integer`), quoted numbers such as `'-12.5E3'` (`QuotedNumber`) and text
+//! literals with a text symbol key such as `'Hello'(001)` (`TextSymbol`). This
+//! module decodes their values and joins literals concatenated with `&`.
+use super::token::{Span, Token};
+
+/// Category of literal tokens.
+pub const LITERAL: &str = "Literal";
+/// Subcategory of text field literals `'...'`.
+pub const TEXT: &str = "Text";
+/// Subcategory of string literals `` `...` ``.
+pub const STRING: &str = "String";
+/// Subcategory of integer literals.
+pub const INTEGER: &str = "Integer";
+/// Subcategory of numbers written as text field literals, such as `'12.5'`.
+pub const QUOTED_NUMBER: &str = "QuotedNumber";
+/// Subcategory of text field literals with a text symbol key.
+pub const TEXT_SYMBOL: &str = "TextSymbol";
+/// Operator joining literals of the same kind.
+pub const CONCATENATION: &str = "&";
+
+/// Decodes the value of a literal token.
+///
+/// The delimiters are removed and doubled delimiters inside the literal,
+/// `''` in text field literals and ``` `` ``` in string literals, are
+/// replaced by a single one. For a text symbol literal, the text without the
+/// key is returned.
+///
+/// # Arguments
+///
+/// * `token` - A token produced by the tokenizer
+///
+/// # Returns
+///
+/// The decoded value, or None if the token is not a literal of a known kind.
+pub fn decode(token: &Token) -> Option<String> {
+    if token.token_type.category != LITERAL {
+        return None;
+    }
+    match token.token_type.subcategory.as_deref()? {
+        TEXT | QUOTED_NUMBER => unquote(&token.value, '\''),
+        TEXT_SYMBOL => {
+            let (literal, _) = token.value.rsplit_once('(')?;
+            unquote(literal, '\'')
+        }
+        STRING => unquote(&token.value, '`'),
+        INTEGER => Some(token.value.clone()),
+        _ => None,
+    }
+}
+
+/// Removes the delimiters of a literal and undoubles escaped delimiters.
+fn unquote(value: &str, delimiter: char) -> Option<String> {
+    let inner = value.strip_prefix(delimiter)?.strip_suffix(delimiter)?;
+    let doubled: String = [delimiter, delimiter].iter().collect();
+    Some(inner.replace(&doubled, &delimiter.to_string()))
+}
+
+/// Joins literals concatenated with the `&` operator into single tokens.
+///
+/// Only literals of the same kind, text field or string, are joined, as in
+/// `'abc' & 'def'`. The joined token spans from the first literal to the last
+/// one, its value is the literal values separated by ` & ` and its
+/// `decoded_value` is the concatenation of their decoded values.
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens of a complete source, in source order
+///
+/// # Returns
+///
+/// The tokens with each chain of concatenated literals replaced by one token.
+pub fn join_literal_concatenations(tokens: Vec<Token>) -> Vec<Token> {
+    let mut joined: Vec<Token> = Vec::with_capacity(tokens.len());
+    let mut iter = tokens.into_iter().peekable();
+    while let Some(token) = iter.next() {
+        let joins = token.symbol() == CONCATENATION
+            && joined.last().is_some_and(is_joinable)
+            && iter.peek().is_some_and(|next| {
+                is_joinable(next)
+                    && joined.last().map(|last| &last.token_type.subcategory)
+                        == Some(&next.token_type.subcategory)
+            });
+        if !joins {
+            joined.push(token);
+            continue;
+        }
+        if let (Some(first), Some(next)) = (joined.last_mut(), iter.next()) {
+            first.value = format!("{} {} {}", first.value, CONCATENATION, next.value);
+            first.decoded_value = match (first.decoded_value.take(), next.decoded_value) {
+                (Some(left), Some(right)) => Some(left + &right),
+                _ => None,
+            };
+            first.span = Span::new(first.span.start, next.span.end);
+        }
+    }
+    joined
+}
+
+/// Returns true if a token is a literal that can be joined with `&`.
+fn is_joinable(token: &Token) -> bool {
+    token.token_type.category == LITERAL
+        && matches!(token.token_type.subcategory.as_deref(), Some(TEXT | STRING))
+}
@@ -12,4 +12,22 @@ pub mod flexible_tokenizer;
 pub mod token;
 
 /// Defines the TokenType structure representing the types of tokens in ABAP code.
-pub mod token_type;
\ No newline at end of file
+pub mod token_type;
+
+/// Reclassifies keywords used as identifiers based on their context.
+pub mod contextual_classifier;
+
+/// Scanning of line and block comments.
+pub mod comments;
+
+/// Scanning support for ABAP string templates.
+pub mod string_template;
+
+/// Groups tokens lexed in embedded-language modes into regions.
+pub mod regions;
+
+/// Merges component paths such as `lo_obj->attr-comp` into single tokens.
+pub mod paths;
+
+/// Decodes typed literals and joins literal concatenations.
+pub mod literals;
@@ -0,0 +1,90 @@
+// abap-tokenizer/src/tokenizer/paths.rs
+//! Grouping of component paths into single tokens.
+//!
+//! A path such as `lo_obj->attr-comp`, `zcl_util=>create` or `sflight~carrid`
+//! is lexed as names separated by `Selector` tokens. Callers that treat a
+//! path as one operand can merge those tokens into a single `Path` token with
+//! `group_paths`.
+use super::token::{Span, Token};
+use super::token_type::TokenType;
+
+/// Category of the tokens produced by `group_paths`.
+pub const PATH: &str = "Path";
+/// Category of the component selectors `-`, `->`, `=>` and `~`.
+pub const SELECTOR: &str = "Selector";
+
+/// Categories of the tokens that can be the components of a path.
+const COMPONENT_CATEGORIES: &[&str] = &["Identifier", "Keyword", "FieldSymbol"];
+
+/// Merges every path of adjacent components and selectors into one token.
+///
+/// A path starts with a component and alternates selectors and components
+/// with no whitespace in between; a single component is left unchanged. The
+/// `Path` token keeps the position of its first component, and its value is
+/// the source text of the whole path.
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens of a complete source, in source order
+///
+/// # Returns
+///
+/// The tokens with each path replaced by a single `Path` token.
+pub fn group_paths(tokens: Vec<Token>) -> Vec<Token> {
+    let mut grouped: Vec<Token> = Vec::with_capacity(tokens.len());
+    let mut index = 0;
+    while index < tokens.len() {
+        let end = path_end(&tokens, index);
+        if end == index {
+            grouped.push(tokens[index].clone());
+        } else {
+            let first = &tokens[index];
+            let mut path = Token::new(
+                TokenType::new(PATH.to_string(), None),
+                tokens[index..=end].iter().map(|t| t.value.as_str()).collect(),
+                first.line,
+                first.column,
+            );
+            path.span = Span::new(first.span.start, tokens[end].span.end);
+            path.mode = first.mode.clone();
+            grouped.push(path);
+        }
+        index = end + 1;
+    }
+    grouped
+}
+
+/// Returns the index of the last component of the path starting at `start`,
+/// or `start` itself if no path starts there.
+fn path_end(tokens: &[Token], start: usize) -> usize {
+    let mut end = start;
+    if !is_component(&tokens[start]) {
+        return end;
+    }
+    while let (Some(selector), Some(component)) = (tokens.get(end + 1), tokens.get(end + 2)) {
+        let joined = is_selector(selector)
+            && is_component(component)
+            && is_adjacent(&tokens[end], selector)
+            && is_adjacent(selector, component);
+        if !joined {
+            break;
+        }
+        end += 2;
+    }
+    end
+}
+
+/// Returns true if there is nothing between two tokens of the same mode.
+fn is_adjacent(previous: &Token, next: &Token) -> bool {
+    previous.span.end == next.span.start && previous.mode == next.mode
+}
+
+/// Returns true if a token can be a component of a path.
+fn is_component(token: &Token) -> bool {
+    COMPONENT_CATEGORIES.contains(&token.token_type.category.as_str())
+}
+
+/// Returns true if a token is a component selector.
+fn is_selector(token: &Token) -> bool {
+    token.token_type.category == SELECTOR
+}
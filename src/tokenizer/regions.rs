@@ -0,0 +1,71 @@
+// abap-tokenizer/src/tokenizer/regions.rs
+//! Embedded-language regions of a token list.
+//!
+//! Code in another language, such as the native SQL between `EXEC SQL` and
+//! `ENDEXEC`, is lexed in a dedicated lexer mode. Every token records the mode
+//! it was produced in, so the regions are the runs of consecutive tokens lexed
+//! outside the main mode.
+use super::token::{Span, Token};
+use crate::config::tokenizer_config::MAIN_MODE;
+use std::ops::Range;
+
+/// A run of consecutive tokens lexed in the same non-main lexer mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// Name of the lexer mode the tokens were produced in.
+    pub mode: String,
+    /// Indices of the region's tokens in the token list.
+    pub tokens: Range<usize>,
+    /// Byte range from the start of the first token to the end of the last one.
+    pub span: Span,
+    /// The line number where the region starts.
+    pub line: usize,
+    /// The column number where the region starts.
+    pub column: usize,
+}
+
+impl Region {
+    /// Returns the source text of the region.
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - The input the tokens were produced from
+    pub fn text<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.span.start..self.span.end]
+    }
+}
+
+/// Finds the embedded-language regions of a token list.
+///
+/// A region ends at the first token of another mode, so a nested mode starts
+/// a new region.
+///
+/// # Arguments
+///
+/// * `tokens` - The tokens of a complete source, in source order
+///
+/// # Returns
+///
+/// The regions, in source order.
+pub fn find_regions(tokens: &[Token]) -> Vec<Region> {
+    let mut regions: Vec<Region> = Vec::new();
+    for (index, token) in tokens.iter().enumerate() {
+        if token.mode == MAIN_MODE {
+            continue;
+        }
+        match regions.last_mut() {
+            Some(region) if region.tokens.end == index && region.mode == token.mode => {
+                region.tokens.end = index + 1;
+                region.span.end = token.span.end;
+            }
+            _ => regions.push(Region {
+                mode: token.mode.clone(),
+                tokens: index..index + 1,
+                span: token.span,
+                line: token.line,
+                column: token.column,
+            }),
+        }
+    }
+    regions
+}
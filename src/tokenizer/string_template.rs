@@ -0,0 +1,80 @@
+// abap-tokenizer/src/tokenizer/string_template.rs
+//! String template support for the ABAP Tokenizer.
+//!
+//! A string template such as `|Hello { lv_name WIDTH = 10 }!\n|` is made of
+//! literal parts and embedded expressions. While inside a template the
+//! tokenizer keeps a stack of `OpenTemplate` values: literal parts are
+//! scanned with `scan_literal`, and embedded expressions are tokenized with
+//! the regular rules until the closing `}`. Templates nested inside an
+//! embedded expression push a new state onto the stack. The escape character
+//! and whether literal parts may span lines come from the `TemplateString`
+//! context rule.
+
+/// Category of the token opening a string template.
+pub const TEMPLATE_START: &str = "TemplateStart";
+/// Category of a literal part of a string template.
+pub const TEMPLATE_LITERAL: &str = "TemplateLiteral";
+/// Category of the `{` opening an embedded expression.
+pub const TEMPLATE_EXPR_OPEN: &str = "TemplateExprOpen";
+/// Category of the `}` closing an embedded expression.
+pub const TEMPLATE_EXPR_CLOSE: &str = "TemplateExprClose";
+/// Category of the token closing a string template.
+pub const TEMPLATE_END: &str = "TemplateEnd";
+
+/// Character opening an embedded expression.
+pub const EXPR_OPEN: char = '{';
+/// Character closing an embedded expression.
+pub const EXPR_CLOSE: char = '}';
+
+/// Lexing state of an open string template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateState {
+    /// Inside a literal part, between the delimiters or after a `}`.
+    Literal,
+    /// Inside an embedded expression, after a `{`.
+    Expression,
+}
+
+/// A string template that has been opened and not yet closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenTemplate {
+    /// Current lexing state of the template.
+    pub state: TemplateState,
+    /// Line of the opening delimiter.
+    pub line: usize,
+    /// Column of the opening delimiter.
+    pub column: usize,
+}
+
+/// Scans a literal part of a string template.
+///
+/// The literal ends before an unescaped template delimiter or an unescaped
+/// `{`, and before a line break unless templates may span lines. An escape
+/// character always consumes the character following it, so with the usual
+/// `\` escape `\|`, `\{`, `\}` and `\\` never end the literal.
+///
+/// # Arguments
+///
+/// * `input` - The input starting at the literal part
+/// * `delimiter` - The character delimiting the template, usually `|`
+/// * `escape` - The escape character, if any, usually `\`
+/// * `multiline` - Whether a literal part may contain line breaks
+///
+/// # Returns
+///
+/// The length of the literal part in bytes.
+pub fn scan_literal(input: &str, delimiter: char, escape: Option<char>, multiline: bool) -> usize {
+    let mut chars = input.char_indices();
+    while let Some((index, ch)) = chars.next() {
+        if Some(ch) == escape {
+            if chars.next().is_none() {
+                return input.len();
+            }
+            continue;
+        }
+        if ch == delimiter || ch == EXPR_OPEN || (ch == '\n' && !multiline) {
+            return index;
+        }
+    }
+    input.len()
+}
@@ -3,7 +3,68 @@
 //!
 //! This module defines the `Token` struct, which represents individual tokens
 //! identified during the tokenization process of ABAP code.
+//!
+//! # Column model
+//!
+//! Lines and columns are 1-based and count characters, not bytes. A token has
+//! two columns:
+//!
+//! - `column` counts every character, including a tab, as one column.
+//! - `visual_column` is the column the token is displayed at: a tab advances
+//!   to the next tab stop, at columns 1, 1 + `tab_width`, 1 + 2 × `tab_width`
+//!   and so on, where `tab_width` is set in the `[metadata]` of the
+//!   configuration. Every other character takes one column.
+//!
+//! Both columns are the same on lines without tabs. The `start_column`
+//! condition of special rules, such as the column-1 `*` comment, is checked
+//! against the visual column.
 use super::token_type::TokenType;
+use crate::config::tokenizer_config::MAIN_MODE;
+
+/// Byte range of a token in the original input.
+///
+/// `start` is inclusive and `end` is exclusive, so `&input[span.start..span.end]`
+/// yields the exact source text the token was produced from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    /// Byte offset where the token starts.
+    pub start: usize,
+    /// Byte offset just past the end of the token.
+    pub end: usize,
+}
+
+impl Span {
+    /// Creates a new Span covering `start..end`.
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    /// Returns the length of the span in bytes.
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns true if the span covers no input.
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// Call site of the macro invocation a token was expanded from.
+///
+/// The position of an expanded token itself is its position in the macro
+/// definition, or at the call site for tokens substituted for a placeholder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroOrigin {
+    /// Name of the expanded macro.
+    pub macro_name: String,
+    /// The line number of the macro invocation.
+    pub call_line: usize,
+    /// The column number of the macro invocation.
+    pub call_column: usize,
+    /// The byte range of the macro invocation, up to its period.
+    pub call_span: Span,
+}
 
 /// Represents a single token in the ABAP code.
 ///
@@ -18,8 +79,25 @@ pub struct Token {
     pub value: String,
     /// The line number where the token appears in the source code.
     pub line: usize,
-    /// The column number where the token starts in its line.
+    /// The column number where the token starts in its line, counting a tab
+    /// as one character.
     pub column: usize,
+    /// The column the token starts at with tabs expanded to the configured
+    /// tab width. See the column model in the module documentation.
+    pub visual_column: usize,
+    /// The byte range of the token in the input.
+    pub span: Span,
+    /// Identifier shared by all tokens produced from the same keyword phrase.
+    pub phrase_id: Option<usize>,
+    /// Name of the lexer mode the token was produced in.
+    pub mode: String,
+    /// The value of a literal without delimiters and with escapes resolved.
+    pub decoded_value: Option<String>,
+    /// The macro invocation the token was expanded from, if any.
+    pub macro_origin: Option<MacroOrigin>,
+    /// Identifier of the source file the token comes from, 0 for the main file.
+    /// `span` is relative to that file.
+    pub file_id: usize,
 }
 
 impl Token {
@@ -41,6 +119,51 @@ impl Token {
             value,
             line,
             column,
+            visual_column: column,
+            span: Span::default(),
+            phrase_id: None,
+            mode: MAIN_MODE.to_string(),
+            decoded_value: None,
+            macro_origin: None,
+            file_id: 0,
+        }
+    }
+
+    /// Returns the token value if the token is a symbol such as `.` or `(`.
+    ///
+    /// Literal text inside string templates and comments can have the same
+    /// value as a symbol; for those tokens an empty string is returned so that
+    /// they are never mistaken for statement delimiters or brackets.
+    pub fn symbol(&self) -> &str {
+        match self.token_type.category.as_str() {
+            "TemplateLiteral" | "Literal" => "",
+            _ if self.is_comment() => "",
+            _ => &self.value,
         }
     }
-}
\ No newline at end of file
+
+    /// Returns true if the token is a comment, including ABAP Doc comments.
+    pub fn is_comment(&self) -> bool {
+        matches!(self.token_type.category.as_str(), "Comment" | "DocComment")
+    }
+}
+
+/// Computes the visual column following a line prefix.
+///
+/// # Arguments
+///
+/// * `prefix` - The text of a line before a position
+/// * `tab_width` - The distance between tab stops
+///
+/// # Returns
+///
+/// The visual column of the position, as defined by the column model.
+pub fn visual_column(prefix: &str, tab_width: usize) -> usize {
+    prefix.chars().fold(1, |column, ch| {
+        if ch == '\t' {
+            (column - 1) / tab_width * tab_width + tab_width + 1
+        } else {
+            column + 1
+        }
+    })
+}
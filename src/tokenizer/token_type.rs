@@ -52,6 +52,7 @@ impl TokenType {
     /// # Panics
     ///
     /// Panics if the input string is not in the correct format.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
         let parts: Vec<&str> = s.split(':').collect();
         match parts.len() {
@@ -0,0 +1,107 @@
+// abap-tokenizer/tests/abap_doc.rs
+//! Tests for ABAP Doc extraction and rendering over the corpus in
+//! `tests/corpus/abap_doc.abap`.
+use abap_tokenizer::config::load_toml_config;
+use abap_tokenizer::doc::{docs_in_file, render_html, render_markdown, DocItem, DocItemKind, FileDocs};
+use std::path::Path;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus/abap_doc.abap");
+
+fn corpus_docs() -> FileDocs {
+    docs_in_file(Path::new(CORPUS), load_toml_config(CONFIG).unwrap()).unwrap()
+}
+
+fn item<'a>(docs: &'a FileDocs, name: &str) -> &'a DocItem {
+    docs.items
+        .iter()
+        .find(|item| item.name == name)
+        .unwrap_or_else(|| panic!("no item {:?}", name))
+}
+
+#[test]
+fn documented_items() {
+    let docs = corpus_docs();
+    let items: Vec<(DocItemKind, &str)> = docs.items.iter().map(|item| (item.kind, item.name.as_str())).collect();
+    assert_eq!(
+        items,
+        [
+            (DocItemKind::Class, "zcl_order"),
+            (DocItemKind::Method, "get_total"),
+            (DocItemKind::Method, "apply_discount"),
+            (DocItemKind::Attribute, "mv_count"),
+        ]
+    );
+}
+
+#[test]
+fn class_doc() {
+    let docs = corpus_docs();
+    let class = item(&docs, "zcl_order");
+    assert_eq!(class.container, None);
+    assert_eq!(class.line, 3);
+    assert_eq!(class.doc.shorttext.as_deref(), Some("Order calculations"));
+    assert_eq!(class.doc.description, "Totals and discounts of sales orders.");
+}
+
+#[test]
+fn method_doc_with_tags() {
+    let docs = corpus_docs();
+    let method = item(&docs, "get_total");
+    assert_eq!(method.container.as_deref(), Some("zcl_order"));
+    assert_eq!(method.doc.summary(), "Calculates the total");
+    assert_eq!(method.doc.parameters.len(), 1);
+    assert_eq!(method.doc.parameters[0].name, "iv_order");
+    assert_eq!(method.doc.parameters[0].text, "Order number");
+    assert_eq!(method.doc.raising[0].name, "zcx_not_found");
+}
+
+#[test]
+fn parameter_docs_are_attached_to_their_method() {
+    let docs = corpus_docs();
+    // The method has no doc of its own; its item holds the parameter docs
+    let method = item(&docs, "apply_discount");
+    assert_eq!(method.kind, DocItemKind::Method);
+    assert_eq!(method.line, 15);
+    assert!(method.doc.description.is_empty());
+    let parameters: Vec<(&str, &str)> = method
+        .doc
+        .parameters
+        .iter()
+        .map(|tag| (tag.name.as_str(), tag.text.as_str()))
+        .collect();
+    assert_eq!(parameters, [("iv_percent", "Discount in percent"), ("iv_round", "Round the result")]);
+}
+
+#[test]
+fn rendered_markdown() {
+    let markdown = render_markdown(&[corpus_docs()]);
+    assert!(markdown.starts_with("# API Documentation\n"));
+    assert!(markdown.contains(&format!("\n## {}\n", CORPUS)));
+    assert!(markdown.contains("### Class `zcl_order`\n\n*Order calculations*\n"));
+    assert!(markdown.contains("#### Method `zcl_order=>get_total`"));
+    assert!(markdown.contains("| Parameter | Description |\n| --- | --- |\n| `iv_order` | Order number |"));
+    assert!(markdown.contains("| `iv_percent` | Discount in percent |"));
+    assert!(markdown.contains("| Exception | Description |"));
+    assert!(!markdown.contains("Method `zcl_order=>iv_percent`"));
+}
+
+#[test]
+fn rendered_html() {
+    let html = render_html(&[corpus_docs()]);
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h3>Class <code>zcl_order</code></h3>"));
+    assert!(html.contains("<h4>Method <code>zcl_order=&gt;get_total</code></h4>"));
+    assert!(html.contains("<tr><td><code>iv_round</code></td><td>Round the result</td></tr>"));
+    assert!(html.trim_end().ends_with("</html>"));
+}
+
+#[test]
+fn files_without_docs_are_not_rendered() {
+    let empty = FileDocs {
+        file: "zempty.prog.abap".to_string(),
+        items: Vec::new(),
+    };
+    assert!(!render_markdown(std::slice::from_ref(&empty)).contains("zempty"));
+    assert!(!render_html(&[empty]).contains("zempty"));
+}
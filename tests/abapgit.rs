@@ -0,0 +1,275 @@
+// abap-tokenizer/tests/abapgit.rs
+//! Tests for reading abapGit repositories, over the checkout in
+//! `tests/corpus/abapgit`: a report with a text pool, a class with a local
+//! definitions part, a program whose XML is in ISO-8859-1, a class whose
+//! XML is in UTF-16 and a module pool with screens in its XML and in a
+//! `.screen_<number>.xml` file.
+use abap_tokenizer::abapgit::metadata::declared_encoding;
+use abap_tokenizer::abapgit::{
+    read_dynpros, read_metadata, AbapGitRepository, ObjectMetadata, ProgramType, TextPoolId,
+};
+use abap_tokenizer::analysis::text_symbols::find_text_symbols;
+use abap_tokenizer::config::{load_toml_config, Dialect, TokenizerConfig};
+use abap_tokenizer::encoding::Encoding;
+use abap_tokenizer::TokenizerError;
+use std::path::{Path, PathBuf};
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+const CHECKOUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus/abapgit");
+
+fn source_file(name: &str) -> PathBuf {
+    Path::new(CHECKOUT).join("src").join(name)
+}
+
+fn config() -> TokenizerConfig {
+    load_toml_config(CONFIG).unwrap()
+}
+
+fn repository() -> AbapGitRepository {
+    AbapGitRepository::scan(Path::new(CHECKOUT), None).unwrap()
+}
+
+fn metadata(repository: &AbapGitRepository, object_type: &str, name: &str) -> ObjectMetadata {
+    repository
+        .object(object_type, name)
+        .and_then(|object| object.metadata.clone())
+        .unwrap_or_else(|| panic!("no metadata for {} {}", object_type, name))
+}
+
+#[test]
+fn scan_groups_files_by_object() {
+    let repository = repository();
+    let objects: Vec<String> = repository.objects.iter().map(ToString::to_string).collect();
+    assert_eq!(objects, ["CLAS ZCL_ORDERS", "CLAS ZCL_UTF16", "PROG ZLATIN1_REPORT", "PROG ZORDERS", "PROG ZSCREENS"]);
+    assert!(repository.objects.iter().all(|object| object.metadata.is_some()));
+
+    // Main file first, then the parts by name
+    let class = repository.object("clas", "zcl_orders").unwrap();
+    let parts: Vec<(Option<&str>, &str)> = class
+        .files
+        .iter()
+        .map(|file| (file.part.as_deref(), file.extension.as_str()))
+        .collect();
+    assert_eq!(parts[0], (None, "abap"));
+    assert!(parts.contains(&(Some("locals_def"), "abap")));
+    assert_eq!(class.source_files().count(), 2);
+}
+
+#[test]
+fn program_metadata() {
+    let metadata = metadata(&repository(), "PROG", "ZORDERS");
+    assert_eq!(metadata.serializer.as_deref(), Some("LCL_OBJECT_PROG"));
+    assert_eq!(metadata.program_type(), Some(ProgramType::Executable));
+    assert_eq!(metadata.attribute("FIXPT"), Some("X"));
+    // The DESCRIPT element in the comment is skipped, so the program title
+    // is used, with its references replaced
+    assert_eq!(metadata.description.as_deref(), Some("Pedidos <abiertos> \u{2014} año"));
+    assert_eq!(metadata.text_pool[0].id, TextPoolId::Title);
+}
+
+#[test]
+fn cdata_sections_are_taken_literally() {
+    let metadata = metadata(&repository(), "PROG", "ZORDERS");
+    let symbol = metadata.text_symbol("001").unwrap();
+    assert_eq!(symbol.entry, "Orders <open> & items");
+    assert_eq!(symbol.length, Some(30));
+}
+
+#[test]
+fn dynpros_in_the_program_xml() {
+    let dynpros = read_dynpros(&source_file("zscreens.prog.xml"), None).unwrap();
+    assert_eq!(dynpros.len(), 1);
+    assert_eq!(dynpros[0].number, "0100");
+    assert_eq!(dynpros[0].description.as_deref(), Some("Orders <main> & items"));
+    // CDATA sections are taken literally
+    assert_eq!(
+        dynpros[0].flow_logic.lines().collect::<Vec<_>>(),
+        [
+            "PROCESS BEFORE OUTPUT.",
+            "  MODULE status_0100. \" </LINE> in a comment",
+            "PROCESS AFTER INPUT.",
+            "  FIELD gv_ok MODULE check ON REQUEST.",
+        ]
+    );
+    // The DESCRIPT element in the comment is not the object description, and
+    // the one of the screen describes the screen only
+    let metadata = metadata(&repository(), "PROG", "ZSCREENS");
+    assert_eq!(metadata.program_type(), Some(ProgramType::ModulePool));
+    assert_eq!(metadata.description.as_deref(), Some("Pedidos <abiertos> \u{2014} año"));
+}
+
+#[test]
+fn dynpros_in_a_screen_file() {
+    let path = source_file("zscreens.prog.screen_0200.xml");
+    assert_eq!(Dialect::for_file(&path), Some(Dialect::Dynpro));
+    let dynpros = read_dynpros(&path, None).unwrap();
+    assert_eq!(dynpros.len(), 1);
+    assert_eq!(dynpros[0].number, "0200");
+    assert_eq!(dynpros[0].description.as_deref(), Some("Order items"));
+    assert_eq!(dynpros[0].flow_logic.lines().count(), 7);
+    assert_eq!(
+        dynpros[0].flow_logic.lines().nth(5),
+        Some("    FIELD: gs_item-matnr, gs_item-menge MODULE check_item.")
+    );
+}
+
+#[test]
+fn screen_flow_logic_is_tokenized_as_parts() {
+    let repository = repository();
+    let object = repository.object("PROG", "ZSCREENS").unwrap();
+    let parts = object.tokenize(&config()).unwrap();
+    let names: Vec<Option<&str>> = parts.iter().map(|part| part.part.as_deref()).collect();
+    assert_eq!(names, [Some("screen_0200"), Some("screen_0100")]);
+    assert!(parts.iter().all(|part| part.dialect == Some(Dialect::Dynpro)));
+
+    let screen = &parts[1];
+    assert!(screen.path.ends_with("zscreens.prog.xml"));
+    let types: Vec<(String, &str)> = screen
+        .tokens
+        .iter()
+        .filter(|token| token.line == 4)
+        .map(|token| (token.token_type.to_string(), token.value.as_str()))
+        .collect();
+    assert_eq!(
+        types,
+        [
+            ("Keyword:Module".to_string(), "FIELD"),
+            ("Identifier".to_string(), "gv_ok"),
+            ("Keyword:Module".to_string(), "MODULE"),
+            ("Identifier".to_string(), "check"),
+            ("Keyword:Module".to_string(), "ON"),
+            ("Keyword:Module".to_string(), "REQUEST"),
+            ("Punctuation".to_string(), "."),
+        ]
+    );
+    let chain = &parts[0];
+    assert!(chain.path.ends_with("zscreens.prog.screen_0200.xml"));
+    // A chained FIELD statement over structure components
+    let types: Vec<(String, &str)> = chain
+        .tokens
+        .iter()
+        .filter(|token| token.line == 6)
+        .map(|token| (token.token_type.to_string(), token.value.as_str()))
+        .take(5)
+        .collect();
+    assert_eq!(
+        types,
+        [
+            ("Keyword:Module".to_string(), "FIELD"),
+            ("Punctuation".to_string(), ":"),
+            ("Identifier".to_string(), "gs_item"),
+            ("Selector:Structure".to_string(), "-"),
+            ("Identifier".to_string(), "matnr"),
+        ]
+    );
+}
+
+#[test]
+fn class_metadata() {
+    let metadata = metadata(&repository(), "CLAS", "ZCL_ORDERS");
+    assert_eq!(metadata.description.as_deref(), Some("Order processing"));
+    assert_eq!(metadata.attribute("EXPOSURE"), Some(""));
+    assert!(metadata.text_pool.is_empty());
+}
+
+#[test]
+fn text_symbols_missing_from_the_pool() {
+    let repository = repository();
+    let reports = repository.reports(&config(), find_text_symbols).unwrap();
+    let report = reports.iter().find(|report| report.name == "ZORDERS").unwrap();
+    let metadata = metadata(&repository, "PROG", "ZORDERS");
+    let missing: Vec<(usize, &str)> = report
+        .parts
+        .iter()
+        .flat_map(|(_, usages)| usages)
+        .filter(|usage| metadata.text_symbol(&usage.key).is_none())
+        .map(|usage| (usage.line, usage.key.as_str()))
+        .collect();
+    assert_eq!(missing, [(2, "002")]);
+    // Keys are compared without regard to case
+    assert!(metadata.text_symbol("00a").is_none());
+    assert_eq!(metadata.text_symbol("003").unwrap().entry, "Total");
+}
+
+#[test]
+fn reports_are_keyed_by_object_and_part() {
+    let reports = repository().reports(&config(), |tokens| tokens.len()).unwrap();
+    let class = reports.iter().find(|report| report.object_type == "CLAS").unwrap();
+    let parts: Vec<Option<&str>> = class.parts.iter().map(|(part, _)| part.as_deref()).collect();
+    assert_eq!(parts, [None, Some("locals_def")]);
+    assert!(class.parts.iter().all(|(_, count)| *count > 0));
+}
+
+#[test]
+fn parse_handles_self_closing_nested_and_commented_elements() {
+    let metadata = ObjectMetadata::parse(
+        "<?xml version=\"1.0\"?><abapGit serializer=\"LCL_OBJECT_INTF\"><asx:abap><asx:values>\
+         <VSEOINTERF><CLSNAME>ZIF_X</CLSNAME><EXPOSURE/>\
+         <DESCRIPT>a &quot;b&quot; &#67;<!-- </DESCRIPT> --> <![CDATA[<d>]]></DESCRIPT></VSEOINTERF>\
+         </asx:values></asx:abap></abapGit>",
+    );
+    assert_eq!(metadata.serializer.as_deref(), Some("LCL_OBJECT_INTF"));
+    assert_eq!(metadata.attribute("EXPOSURE"), Some(""));
+    assert_eq!(metadata.attribute("CLSNAME"), Some("ZIF_X"));
+    assert_eq!(metadata.description.as_deref(), Some("a \"b\" C <d>"));
+}
+
+#[test]
+fn metadata_in_declared_latin1_encoding() {
+    let metadata = metadata(&repository(), "PROG", "ZLATIN1_REPORT");
+    assert_eq!(metadata.program_type(), Some(ProgramType::Executable));
+    let symbol = metadata.text_symbol("001").unwrap();
+    assert_eq!(symbol.entry, "Größe & Maße");
+    assert_eq!(symbol.length, Some(20));
+    assert_eq!(metadata.description.as_deref(), Some("Informe en español"));
+}
+
+#[test]
+fn metadata_in_utf16_with_byte_order_mark() {
+    let metadata = metadata(&repository(), "CLAS", "ZCL_UTF16");
+    assert_eq!(metadata.description.as_deref(), Some("Übersicht der Aufträge"));
+    assert_eq!(metadata.attribute("LANGU"), Some("D"));
+}
+
+#[test]
+fn given_encoding_takes_precedence_over_the_declaration() {
+    let path = source_file("zlatin1_report.prog.xml");
+    let result = read_metadata(&path, Some(Encoding::Utf8));
+    assert!(
+        matches!(result, Err(TokenizerError::EncodingError(ref message)) if message.contains("zlatin1_report.prog.xml")),
+        "{:?}",
+        result
+    );
+    let metadata = read_metadata(&path, Some(Encoding::Windows1252)).unwrap();
+    assert_eq!(metadata.text_symbol("001").unwrap().entry, "Größe & Maße");
+}
+
+#[test]
+fn byte_order_mark_takes_precedence_over_the_given_encoding() {
+    let metadata = read_metadata(&source_file("zcl_utf16.clas.xml"), Some(Encoding::Latin1)).unwrap();
+    assert_eq!(metadata.description.as_deref(), Some("Übersicht der Aufträge"));
+}
+
+#[test]
+fn encoding_declarations() {
+    let declared = |xml: &str| declared_encoding(xml.as_bytes());
+    assert_eq!(declared("<?xml version=\"1.0\" encoding=\"utf-8\"?><a/>"), Some(Encoding::Utf8));
+    assert_eq!(declared("<?xml version='1.0' encoding='ISO-8859-1'?><a/>"), Some(Encoding::Latin1));
+    assert_eq!(declared("<?xml version=\"1.0\" encoding=\"windows-1252\" standalone=\"yes\"?>"), Some(Encoding::Windows1252));
+    assert_eq!(declared("<?xml version=\"1.0\"?><a encoding=\"latin1\"/>"), None);
+    assert_eq!(declared("<?xml version=\"1.0\" encoding=\"ebcdic\"?>"), None);
+    assert_eq!(declared("<a/>"), None);
+}
+
+#[test]
+fn sources_are_decoded_with_the_configured_encoding() {
+    let mut config = config();
+    config.metadata.encoding = Some(Encoding::Latin1);
+    let repository = repository();
+    let object = repository.object("PROG", "ZLATIN1_REPORT").unwrap();
+    let parts = object.tokenize(&config).unwrap();
+    assert_eq!(parts[0].source.encoding, Encoding::Latin1);
+    assert!(parts[0].tokens.iter().any(|token| token.value == "TEXT-001"));
+    // Without it the source is read as UTF-8
+    assert_eq!(object.tokenize(&self::config()).unwrap()[0].source.encoding, Encoding::Utf8);
+}
@@ -0,0 +1,83 @@
+// abap-tokenizer/tests/amdp.rs
+//! Tests for AMDP methods, whose bodies are lexed in the `sqlscript` mode,
+//! over the corpus in `tests/corpus/amdp.abap`.
+use abap_tokenizer::config::load_toml_config;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use std::fs;
+use std::ops::RangeInclusive;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus/amdp.abap");
+
+fn tokenize(source: &str) -> Vec<Token> {
+    FlexibleTokenizer::new(source, load_toml_config(CONFIG).unwrap())
+        .tokenize()
+        .unwrap()
+}
+
+fn corpus_tokens() -> Vec<Token> {
+    tokenize(&fs::read_to_string(CORPUS).unwrap())
+}
+
+/// Returns the type of the token with the given value on a line. Panics if
+/// there is none, so that a wrong line number does not pass silently.
+fn type_of(tokens: &[Token], line: usize, value: &str) -> String {
+    tokens
+        .iter()
+        .find(|token| token.line == line && token.value == value)
+        .unwrap_or_else(|| panic!("no token {:?} on line {}", value, line))
+        .token_type
+        .to_string()
+}
+
+fn modes_of_lines(tokens: &[Token], lines: RangeInclusive<usize>) -> Vec<&str> {
+    tokens
+        .iter()
+        .filter(|token| lines.contains(&token.line))
+        .map(|token| token.mode.as_str())
+        .collect()
+}
+
+#[test]
+fn amdp_header_is_lexed_as_abap() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 3, "LANGUAGE SQLSCRIPT"), "Keyword:Amdp");
+    assert!(modes_of_lines(&tokens, 1..=5).iter().all(|mode| *mode == "main"));
+}
+
+#[test]
+fn amdp_body_is_lexed_as_sqlscript() {
+    let tokens = corpus_tokens();
+    assert!(modes_of_lines(&tokens, 6..=9).iter().all(|mode| *mode == "sqlscript"));
+    assert_eq!(type_of(&tokens, 6, "-- SQLScript comment\n"), "Comment");
+    assert_eq!(type_of(&tokens, 7, "\"CARRID\""), "Identifier:Quoted");
+    assert_eq!(type_of(&tokens, 9, ":iv_carrid"), "HostVariable");
+}
+
+#[test]
+fn endmethod_returns_to_abap() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 10, "ENDMETHOD"), "Keyword:Subroutine");
+    assert!(modes_of_lines(&tokens, 10..=15).iter().all(|mode| *mode == "main"));
+    assert_eq!(type_of(&tokens, 13, "\" ABAP comment\n"), "Comment");
+}
+
+#[test]
+fn language_sqlscript_outside_an_amdp_header_stays_abap() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 12, "LANGUAGE"), "Identifier");
+    assert_eq!(type_of(&tokens, 12, "sqlscript"), "Identifier");
+}
+
+#[test]
+fn database_function_header_enters_sqlscript() {
+    let tokens = tokenize(
+        "METHOD get BY DATABASE FUNCTION FOR HDB LANGUAGE SQLSCRIPT OPTIONS READ-ONLY.\n\
+         \x20 RETURN SELECT \"A\" FROM t;\n\
+         ENDMETHOD.\n",
+    );
+    assert!(modes_of_lines(&tokens, 2..=2).iter().all(|mode| *mode == "sqlscript"));
+    assert_eq!(type_of(&tokens, 2, "\"A\""), "Identifier:Quoted");
+    assert_eq!(type_of(&tokens, 3, "ENDMETHOD"), "Keyword:Subroutine");
+}
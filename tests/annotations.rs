@@ -0,0 +1,91 @@
+// abap-tokenizer/tests/annotations.rs
+//! Tests for pragmas and pseudo-comments: their codes and parameters, and
+//! the statements they are attached to.
+use abap_tokenizer::analysis::annotations::{find_annotations, AnnotatedStatement, AnnotationKind};
+use abap_tokenizer::config::load_toml_config;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+fn annotate(source: &str) -> Vec<AnnotatedStatement> {
+    let tokens = FlexibleTokenizer::new(source, load_toml_config(CONFIG).unwrap())
+        .tokenize()
+        .unwrap();
+    find_annotations(&tokens)
+}
+
+/// Returns the source text of a statement.
+fn text<'a>(source: &'a str, statement: &AnnotatedStatement) -> &'a str {
+    &source[statement.span.start..statement.span.end]
+}
+
+#[test]
+fn pragma_with_parameters() {
+    let source = "DATA lv_x TYPE i ##SHADOW[lv_x] ##needed.";
+    let statements = annotate(source);
+    assert_eq!(statements.len(), 1);
+    assert_eq!(text(source, &statements[0]), source);
+    let annotations = &statements[0].annotations;
+    assert_eq!(annotations.len(), 2);
+    assert_eq!(annotations[0].kind, AnnotationKind::Pragma);
+    assert_eq!(annotations[0].code, "SHADOW");
+    assert_eq!(annotations[0].parameters, ["lv_x"]);
+    assert_eq!((annotations[0].line, annotations[0].column), (1, 18));
+    // Codes are upper-cased and `has_code` ignores case
+    assert_eq!(annotations[1].code, "NEEDED");
+    assert!(annotations[1].parameters.is_empty());
+    assert!(statements[0].has_code("needed"));
+}
+
+#[test]
+fn pragma_with_several_parameters() {
+    let statements = annotate("CALL METHOD lo_x->run ##SHADOW[a][b].");
+    assert_eq!(statements[0].annotations[0].parameters, ["a", "b"]);
+}
+
+#[test]
+fn pseudo_comment_with_several_codes() {
+    let statements = annotate("SELECT * FROM sflight INTO TABLE lt_f \"#EC CI_NOWHERE ci_genbuff\n  WHERE carrid = 'LH'.");
+    assert_eq!(statements.len(), 1);
+    let codes: Vec<&str> = statements[0].annotations.iter().map(|annotation| annotation.code.as_str()).collect();
+    assert_eq!(codes, ["CI_NOWHERE", "CI_GENBUFF"]);
+    assert!(statements[0]
+        .annotations
+        .iter()
+        .all(|annotation| annotation.kind == AnnotationKind::PseudoComment && annotation.line == 1));
+}
+
+#[test]
+fn pseudo_comment_after_the_period() {
+    let source = "SELECT * FROM sflight INTO TABLE lt_f. \"#EC CI_NOWHERE\nWRITE lv_x.";
+    let statements = annotate(source);
+    assert_eq!(statements.len(), 1);
+    assert_eq!(text(source, &statements[0]), "SELECT * FROM sflight INTO TABLE lt_f.");
+    assert!(statements[0].has_code("CI_NOWHERE"));
+}
+
+#[test]
+fn pseudo_comment_on_its_own_line_applies_to_the_next_statement() {
+    let source = "WRITE lv_a.\n\"#EC NOTEXT\nWRITE 'b'.";
+    let statements = annotate(source);
+    assert_eq!(statements.len(), 1);
+    // The statement starts at the annotation leading it
+    assert_eq!(text(source, &statements[0]), "\"#EC NOTEXT\nWRITE 'b'.");
+    assert_eq!(statements[0].line, 2);
+}
+
+#[test]
+fn pragma_before_the_next_statement() {
+    // A pragma after a period belongs to the statement that follows it
+    let source = "CLEAR lv_a. ##NEEDED DATA lv_b TYPE i.";
+    let statements = annotate(source);
+    assert_eq!(statements.len(), 1);
+    assert_eq!(text(source, &statements[0]), "##NEEDED DATA lv_b TYPE i.");
+    assert_eq!((statements[0].line, statements[0].column), (1, 13));
+    assert!(statements[0].has_code("NEEDED"));
+}
+
+#[test]
+fn ordinary_comments_are_not_annotations() {
+    assert!(annotate("WRITE lv_x. \" #EC is not at the start\n* \"#EC NOTEXT\n").is_empty());
+}
@@ -0,0 +1,127 @@
+// abap-tokenizer/tests/bdef.rs
+//! Tests for the behavior definition profile over
+//! `tests/corpus/zi_flight.bdef.asbdef`: the header, the entity definition,
+//! the operations of the behavior body and the field mapping.
+use abap_tokenizer::config::Dialect;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use std::fs;
+use std::path::Path;
+
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus/zi_flight.bdef.asbdef");
+
+fn corpus_tokens() -> Vec<Token> {
+    let source = fs::read_to_string(CORPUS).unwrap();
+    FlexibleTokenizer::new(&source, Dialect::Bdef.config().unwrap())
+        .tokenize()
+        .unwrap()
+}
+
+/// Returns the types and values of the tokens on a line, skipping comments.
+fn line_tokens(tokens: &[Token], line: usize) -> Vec<(String, String)> {
+    tokens
+        .iter()
+        .filter(|token| token.line == line && token.token_type.category != "Comment")
+        .map(|token| (token.token_type.to_string(), token.value.clone()))
+        .collect()
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|(token_type, value)| (token_type.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn implementation_header() {
+    let tokens = corpus_tokens();
+    assert_eq!(
+        line_tokens(&tokens, 1),
+        pairs(&[
+            ("Keyword:Implementation", "managed"),
+            ("Keyword:Implementation", "implementation in class"),
+            ("Identifier", "zbp_i_flight"),
+            ("Keyword:Implementation", "unique"),
+            ("Punctuation", ";"),
+        ])
+    );
+    assert_eq!(line_tokens(&tokens, 2)[2], ("Literal:Number".to_string(), "2".to_string()));
+}
+
+#[test]
+fn entity_definition() {
+    let tokens = corpus_tokens();
+    assert_eq!(
+        line_tokens(&tokens, 4),
+        pairs(&[
+            ("Keyword:Entity", "define behavior for"),
+            ("Identifier", "ZI_Flight"),
+            ("Keyword:Entity", "alias"),
+            ("Identifier", "Flight"),
+        ])
+    );
+    assert_eq!(line_tokens(&tokens, 5)[2], ("Identifier:Namespaced".to_string(), "/dmo/flight".to_string()));
+    assert_eq!(line_tokens(&tokens, 6), pairs(&[("Keyword:Entity", "lock master")]));
+}
+
+#[test]
+fn behavior_body() {
+    let tokens = corpus_tokens();
+    assert_eq!(line_tokens(&tokens, 10), pairs(&[("Keyword:Operation", "create"), ("Punctuation", ";")]));
+    assert_eq!(
+        line_tokens(&tokens, 14)[..4],
+        pairs(&[
+            ("Keyword:Field", "field"),
+            ("Punctuation", "("),
+            ("Keyword:Field", "readonly"),
+            ("Punctuation", ")"),
+        ])
+    );
+    let action = line_tokens(&tokens, 17);
+    assert_eq!(action[6], ("Identifier".to_string(), "cancel".to_string()));
+    assert_eq!(
+        action[7..10],
+        pairs(&[
+            ("Keyword:Operation", "result"),
+            ("Selector:Cardinality", "[1]"),
+            ("Identifier:Builtin", "$self"),
+        ])
+    );
+    assert_eq!(
+        line_tokens(&tokens, 18)[..3],
+        pairs(&[
+            ("Keyword:Behavior", "determination"),
+            ("Identifier", "setCurrency"),
+            ("Keyword:Behavior", "on modify"),
+        ])
+    );
+    assert_eq!(line_tokens(&tokens, 19)[2], ("Keyword:Behavior".to_string(), "on save".to_string()));
+}
+
+#[test]
+fn field_mapping_and_comments() {
+    let tokens = corpus_tokens();
+    assert_eq!(
+        line_tokens(&tokens, 23),
+        pairs(&[
+            ("Identifier", "CarrierId"),
+            ("Operator", "="),
+            ("Identifier", "carrier_id"),
+            ("Punctuation", ";"),
+        ])
+    );
+    let comments: Vec<_> = tokens
+        .iter()
+        .filter(|token| token.token_type.category == "Comment")
+        .map(|token| (token.line, token.value.trim_end()))
+        .collect();
+    assert_eq!(comments, [(13, "// Keys are set on creation only")]);
+}
+
+#[test]
+fn profile_is_selected_by_file_extension() {
+    assert_eq!(Dialect::for_file(Path::new("zi_flight.bdef.asbdef")), Some(Dialect::Bdef));
+    assert_eq!("bdef".parse::<Dialect>(), Ok(Dialect::Bdef));
+    assert_eq!(Dialect::from_language_version("ABAP BDEF"), Some(Dialect::Bdef));
+}
@@ -0,0 +1,157 @@
+// abap-tokenizer/tests/cds.rs
+//! Tests for the CDS DDL profile over `tests/corpus/zi_flight.ddls.asddls`:
+//! annotations, the `define view entity` header, associations with their
+//! cardinalities and the selection of the profile by file extension.
+use abap_tokenizer::config::Dialect;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use std::fs;
+use std::path::Path;
+
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus/zi_flight.ddls.asddls");
+
+fn corpus_tokens() -> Vec<Token> {
+    let source = fs::read_to_string(CORPUS).unwrap();
+    FlexibleTokenizer::new(&source, Dialect::Cds.config().unwrap())
+        .tokenize()
+        .unwrap()
+}
+
+/// Returns the types and values of the tokens on a line, skipping comments.
+fn line_tokens(tokens: &[Token], line: usize) -> Vec<(String, String)> {
+    tokens
+        .iter()
+        .filter(|token| token.line == line && token.token_type.category != "Comment")
+        .map(|token| (token.token_type.to_string(), token.value.clone()))
+        .collect()
+}
+
+fn pairs(expected: &[(&str, &str)]) -> Vec<(String, String)> {
+    expected
+        .iter()
+        .map(|(token_type, value)| (token_type.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn annotations() {
+    let tokens = corpus_tokens();
+    assert_eq!(
+        line_tokens(&tokens, 1),
+        pairs(&[
+            ("Annotation", "@AbapCatalog.viewEnhancementCategory"),
+            ("Punctuation", ":"),
+            ("Punctuation", "["),
+            ("Literal:Enum", "#NONE"),
+            ("Punctuation", "]"),
+        ])
+    );
+    assert_eq!(line_tokens(&tokens, 3)[2], ("Literal:String".to_string(), "'Flights'".to_string()));
+    // Annotation names are case-sensitive
+    assert_eq!(
+        line_tokens(&tokens, 4),
+        pairs(&[
+            ("Annotation:Unknown", "@myCompany.reviewed"),
+            ("Punctuation", ":"),
+            ("Literal:Boolean", "true"),
+        ])
+    );
+    // Element annotations inside the projection list
+    assert_eq!(line_tokens(&tokens, 15)[0], ("Annotation".to_string(), "@Semantics.amount.currencyCode".to_string()));
+}
+
+#[test]
+fn define_view_entity() {
+    let tokens = corpus_tokens();
+    assert_eq!(
+        line_tokens(&tokens, 5),
+        pairs(&[
+            ("Keyword:Definition", "define"),
+            ("Keyword:Definition", "view"),
+            ("Keyword:Definition", "entity"),
+            ("Identifier", "ZI_Flight"),
+        ])
+    );
+    assert_eq!(
+        line_tokens(&tokens, 6),
+        pairs(&[
+            ("Keyword:Query", "as"),
+            ("Keyword:Query", "select"),
+            ("Keyword:Query", "from"),
+            ("Identifier", "sflight"),
+            ("Keyword:Query", "as"),
+            ("Identifier", "Flight"),
+        ])
+    );
+    assert_eq!(
+        line_tokens(&tokens, 13),
+        pairs(&[
+            ("Keyword:Query", "key"),
+            ("Identifier", "Flight"),
+            ("Selector:Path", "."),
+            ("Identifier", "carrid"),
+            ("Keyword:Query", "as"),
+            ("Identifier", "CarrierId"),
+            ("Punctuation", ","),
+        ])
+    );
+}
+
+#[test]
+fn associations() {
+    let tokens = corpus_tokens();
+    assert_eq!(
+        line_tokens(&tokens, 7),
+        pairs(&[
+            ("Keyword:Association", "association"),
+            ("Selector:Cardinality", "[0..*]"),
+            ("Keyword:Association", "to"),
+            ("Identifier", "ZI_Booking"),
+            ("Keyword:Query", "as"),
+            ("Identifier", "_Booking"),
+        ])
+    );
+    assert_eq!(
+        line_tokens(&tokens, 9)[..3],
+        pairs(&[
+            ("Keyword:Logical", "and"),
+            ("Identifier:Builtin", "$projection"),
+            ("Selector:Path", "."),
+        ])
+    );
+    let carrier = line_tokens(&tokens, 10);
+    assert_eq!(carrier[1], ("Selector:Cardinality".to_string(), "[1]".to_string()));
+    assert_eq!(carrier[3], ("Identifier:Namespaced".to_string(), "/dmo/I_Carrier".to_string()));
+    // Exposed associations are plain names
+    assert_eq!(line_tokens(&tokens, 19), pairs(&[("Identifier", "_Booking"), ("Punctuation", ",")]));
+}
+
+#[test]
+fn comments() {
+    let tokens = corpus_tokens();
+    let comments: Vec<_> = tokens
+        .iter()
+        .filter(|token| token.token_type.category == "Comment")
+        .map(|token| (token.line, token.value.trim_end()))
+        .collect();
+    assert_eq!(
+        comments,
+        [
+            (12, "// Key fields"),
+            (16, "-- in the flight currency"),
+            (18, "/* Associations */"),
+        ]
+    );
+}
+
+#[test]
+fn profile_is_selected_by_file_extension() {
+    for file in ["zi_flight.ddls.asddls", "ZI_FLIGHT.DDLS.ASDDLS", "zc_flight.ddlx.asddlxs", "zi_flight.dcls.asdcls"] {
+        assert_eq!(Dialect::for_file(Path::new(file)), Some(Dialect::Cds), "{}", file);
+    }
+    for file in ["zreport.prog.abap", "zi_flight.ddls.xml", "README"] {
+        assert_eq!(Dialect::for_file(Path::new(file)), None, "{}", file);
+    }
+    assert_eq!("cds".parse::<Dialect>(), Ok(Dialect::Cds));
+    assert_eq!(Dialect::from_language_version("ABAP CDS"), Some(Dialect::Cds));
+}
@@ -0,0 +1,107 @@
+// abap-tokenizer/tests/columns.rs
+//! Tests for the column model: character columns, tab-aware visual columns,
+//! `start_column` rules checked against visual columns and the `tab_width`
+//! setting.
+use abap_tokenizer::config::{load_toml_config, TokenizerConfig};
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::{visual_column, Token};
+use abap_tokenizer::ConfigError;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+fn config() -> TokenizerConfig {
+    load_toml_config(CONFIG).unwrap()
+}
+
+fn tokenize_with(source: &str, config: TokenizerConfig) -> Vec<Token> {
+    FlexibleTokenizer::new(source, config).tokenize().unwrap()
+}
+
+fn find<'a>(tokens: &'a [Token], value: &str) -> &'a Token {
+    tokens
+        .iter()
+        .find(|token| token.value == value)
+        .unwrap_or_else(|| panic!("no token {:?}", value))
+}
+
+#[test]
+fn visual_column_of_line_prefixes() {
+    assert_eq!(visual_column("", 8), 1);
+    assert_eq!(visual_column("ab", 8), 3);
+    assert_eq!(visual_column("\t", 8), 9);
+    assert_eq!(visual_column("ab\t", 8), 9);
+    assert_eq!(visual_column("abcdefgh\t", 8), 17);
+    assert_eq!(visual_column("\t\t", 4), 9);
+    assert_eq!(visual_column("a\tb\t", 4), 9);
+    // Characters count once, whatever their width in bytes
+    assert_eq!(visual_column("ñé\t", 8), 9);
+}
+
+#[test]
+fn tabs_advance_visual_but_not_character_columns() {
+    let tokens = tokenize_with("DATA\tlv_a TYPE i.\n\tWRITE\tlv_a.", config());
+    let lv_a = find(&tokens, "lv_a");
+    assert_eq!((lv_a.column, lv_a.visual_column), (6, 9));
+    let write = find(&tokens, "WRITE");
+    assert_eq!((write.line, write.column, write.visual_column), (2, 2, 9));
+    // The column after a tab follows the previous tab stop
+    let last = tokens.iter().filter(|token| token.value == "lv_a").nth(1).unwrap();
+    assert_eq!((last.column, last.visual_column), (8, 17));
+}
+
+#[test]
+fn star_after_a_tab_is_not_a_full_line_comment() {
+    // `*` only starts a comment in visual column 1
+    let tokens = tokenize_with("\t* 2.\n* comment\n", config());
+    assert_eq!(tokens[0].value, "*");
+    assert_ne!(tokens[0].token_type.category, "Comment");
+    assert_eq!(tokens[0].visual_column, 9);
+    let comment = tokens.last().unwrap();
+    assert_eq!(comment.token_type.category, "Comment");
+    assert_eq!((comment.line, comment.visual_column), (2, 1));
+}
+
+#[test]
+fn visual_columns_of_tokens_spanning_lines() {
+    // The comment ends with the line break, the next token starts a line
+    let tokens = tokenize_with("\tlv_a = 1. \" note\n\t\tlv_b = 2.", config());
+    let comment = tokens.iter().find(|token| token.token_type.category == "Comment").unwrap();
+    assert_eq!(comment.visual_column, 19);
+    assert_eq!(find(&tokens, "lv_b").visual_column, 17);
+}
+
+#[test]
+fn tab_width_override() {
+    let mut config = config();
+    config.metadata.tab_width = 4;
+    let tokens = tokenize_with("\tDATA\tlv_a.", config);
+    assert_eq!(find(&tokens, "DATA").visual_column, 5);
+    assert_eq!(find(&tokens, "lv_a").visual_column, 13);
+
+    // With a tab width of 1 a tab counts as one column, so a `*` after a tab
+    // is still not in column 1
+    let mut config = self::config();
+    config.metadata.tab_width = 1;
+    let tokens = tokenize_with("\t* 2.", config);
+    assert_eq!(tokens[0].visual_column, 2);
+    assert_ne!(tokens[0].token_type.category, "Comment");
+}
+
+#[test]
+fn tab_width_in_the_configuration_file() {
+    let content = std::fs::read_to_string(CONFIG)
+        .unwrap()
+        .replace("tab_width = 8", "tab_width = 2")
+        // Imports are relative to the configuration file
+        .replace("\"dialects/", &format!("\"{}/config/dialects/", env!("CARGO_MANIFEST_DIR")));
+    let path = std::env::temp_dir().join(format!("abap-tokenizer-tab-width-{}.toml", std::process::id()));
+    std::fs::write(&path, &content).unwrap();
+    let config = load_toml_config(path.to_str().unwrap()).unwrap();
+    assert_eq!(config.metadata.tab_width, 2);
+
+    std::fs::write(&path, content.replace("tab_width = 2", "tab_width = 0")).unwrap();
+    assert!(matches!(
+        load_toml_config(path.to_str().unwrap()),
+        Err(ConfigError::ConfigurationError(_))
+    ));
+}
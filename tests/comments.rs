@@ -0,0 +1,131 @@
+// abap-tokenizer/tests/comments.rs
+//! Tests for block comments: nesting, comments left open at the end of the
+//! input and the position of the diagnostic reporting them.
+use abap_tokenizer::config::{load_toml_config, Dialect, TokenizerConfig};
+use abap_tokenizer::parser::parse;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use abap_tokenizer::{ConfigError, Diagnostic, Severity};
+use std::fs;
+
+/// Writes configuration files into a fresh scratch directory and loads the
+/// first one.
+fn load_configs(test: &str, files: &[(&str, &str)]) -> Result<TokenizerConfig, ConfigError> {
+    let dir = std::env::temp_dir().join(format!("abap-tokenizer-comments-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for (name, content) in files {
+        fs::write(dir.join(name), content).unwrap();
+    }
+    load_toml_config(dir.join(files[0].0).to_str().unwrap())
+}
+
+/// Returns a configuration with words and `/* */` block comments.
+fn config(test: &str, nested: bool) -> TokenizerConfig {
+    let content = format!(
+        "[metadata]\nlanguage_version = \"test\"\ncase_sensitive = false\nallow_unicode_identifiers = false\n\n\
+         [token_categories]\nWord = {{ priority = 1, color = \"black\" }}\nPunctuation = {{ priority = 2, color = \"gray\" }}\n\n\
+         [patterns]\nWord = [ {{ regex = \"[a-z0-9]+\" }} ]\nPunctuation = [ {{ regex = \"[.*/]\" }} ]\n\n\
+         [[comments]]\nstart = \"/*\"\nend = \"*/\"\nnested = {}\n",
+        nested
+    );
+    load_configs(test, &[("config.toml", &content)]).unwrap()
+}
+
+fn tokenize(source: &str, config: TokenizerConfig) -> (Vec<Token>, Vec<Diagnostic>) {
+    let mut tokenizer = FlexibleTokenizer::new(source, config);
+    let tokens = tokenizer.tokenize().unwrap();
+    (tokens, tokenizer.diagnostics().to_vec())
+}
+
+fn comments(tokens: &[Token]) -> Vec<&str> {
+    tokens
+        .iter()
+        .filter(|token| token.is_comment())
+        .map(|token| token.value.as_str())
+        .collect()
+}
+
+#[test]
+fn nested_comment_of_depth_two() {
+    let (tokens, diagnostics) = tokenize("a /* x /* y */ z */ b", config("nested-2", true));
+    assert_eq!(comments(&tokens), ["/* x /* y */ z */"]);
+    assert_eq!(tokens.last().unwrap().value, "b");
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn nested_comment_of_depth_three_across_lines() {
+    let source = "/* 1\n  /* 2 /* 3 */ 2 */\n1 */ b\n/* c */ d";
+    let (tokens, diagnostics) = tokenize(source, config("nested-3", true));
+    assert_eq!(comments(&tokens), ["/* 1\n  /* 2 /* 3 */ 2 */\n1 */", "/* c */"]);
+    let b = tokens.iter().find(|token| token.value == "b").unwrap();
+    assert_eq!((b.line, b.column), (3, 6));
+    let d = tokens.iter().find(|token| token.value == "d").unwrap();
+    assert_eq!((d.line, d.column), (4, 9));
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn comments_do_not_nest_unless_configured() {
+    let (tokens, diagnostics) = tokenize("a /* x /* y */ z */ b", config("not-nested", false));
+    assert_eq!(comments(&tokens), ["/* x /* y */"]);
+    let rest: Vec<&str> = tokens.iter().skip(2).map(|token| token.value.as_str()).collect();
+    assert_eq!(rest, ["z", "*", "/", "b"]);
+    assert!(diagnostics.is_empty());
+}
+
+#[test]
+fn end_of_input_inside_a_nested_comment() {
+    // The inner comment is closed, the outer one is not
+    let source = "a.\n  b /* x\n /* y */ z";
+    let (tokens, diagnostics) = tokenize(source, config("eof-outer", true));
+    assert_eq!(comments(&tokens), ["/* x\n /* y */ z"]);
+    assert_eq!(tokens.last().unwrap().value, "/* x\n /* y */ z");
+    assert_eq!(diagnostics.len(), 1);
+    let diagnostic = &diagnostics[0];
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!((diagnostic.line, diagnostic.column), (2, 5));
+    assert!(diagnostic.message.contains("unterminated block comment"), "{}", diagnostic.message);
+    assert!(diagnostic.message.contains("/*"), "{}", diagnostic.message);
+}
+
+#[test]
+fn end_of_input_at_depth_two() {
+    // Neither comment is closed: one token and one diagnostic, at the
+    // outermost opening delimiter
+    let (tokens, diagnostics) = tokenize("x /* a /* b */", config("eof-inner", true));
+    assert_eq!(comments(&tokens), ["/* a /* b */"]);
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 3));
+}
+
+#[test]
+fn unterminated_comment_in_cds() {
+    let source = "define view entity ZI_Demo as select from t\n{\n  key id /* the key\n}\n";
+    let mut tokenizer = FlexibleTokenizer::new(source, Dialect::Cds.config().unwrap());
+    let tokens = tokenizer.tokenize().unwrap();
+    assert_eq!(tokens.last().unwrap().value, "/* the key\n}\n");
+    let diagnostics = tokenizer.diagnostics();
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 10));
+}
+
+#[test]
+fn unterminated_comment_is_reported_by_the_parser() {
+    let tree = parse("a /* x /* y */", config("parser", true)).unwrap();
+    let diagnostic = tree
+        .diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.message.contains("unterminated block comment"))
+        .unwrap();
+    assert_eq!((diagnostic.line, diagnostic.column), (1, 3));
+}
+
+#[test]
+fn line_comment_cannot_be_nested() {
+    let content = "[metadata]\nlanguage_version = \"test\"\ncase_sensitive = false\nallow_unicode_identifiers = false\n\n\
+                   [[comments]]\nstart = \"--\"\nnested = true\n";
+    let result = load_configs("nested-line", &[("config.toml", content)]);
+    assert!(matches!(result, Err(ConfigError::ConfigurationError(_))), "{:?}", result.err());
+}
@@ -0,0 +1,106 @@
+// abap-tokenizer/tests/contextual_rules.rs
+//! Tests for the reclassification of keywords used as identifiers, run over
+//! the corpus in `tests/corpus/keywords_as_identifiers.abap`.
+use abap_tokenizer::config::{load_toml_config, TokenizerConfig};
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use std::fs;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus/keywords_as_identifiers.abap");
+
+fn tokenize(source: &str, config: TokenizerConfig) -> Vec<Token> {
+    FlexibleTokenizer::new(source, config).tokenize().unwrap()
+}
+
+fn corpus_tokens() -> Vec<Token> {
+    tokenize(&fs::read_to_string(CORPUS).unwrap(), load_toml_config(CONFIG).unwrap())
+}
+
+/// Returns the type of the token with the given value on a line. Panics if
+/// there is none, so that a wrong line number does not pass silently.
+fn type_of(tokens: &[Token], line: usize, value: &str) -> String {
+    tokens
+        .iter()
+        .find(|token| token.line == line && token.value == value)
+        .unwrap_or_else(|| panic!("no token {:?} on line {}", value, line))
+        .token_type
+        .to_string()
+}
+
+/// Writes a configuration into a scratch file and loads it.
+fn load_config(test: &str, content: &str) -> TokenizerConfig {
+    let path = std::env::temp_dir().join(format!("abap-tokenizer-{}-{}.toml", test, std::process::id()));
+    fs::write(&path, content).unwrap();
+    load_toml_config(path.to_str().unwrap()).unwrap()
+}
+
+#[test]
+fn declaration_names() {
+    let tokens = corpus_tokens();
+    for (line, name) in [(3, "type"), (4, "value"), (5, "where"), (6, "select"), (7, "data")] {
+        assert_eq!(type_of(&tokens, line, name), "Identifier", "line {}", line);
+    }
+    // The keyword following the name is left alone
+    assert_eq!(type_of(&tokens, 3, "TYPE"), "Keyword:Declaration");
+    assert_eq!(type_of(&tokens, 6, "VALUE"), "Keyword:Declaration");
+}
+
+#[test]
+fn component_names() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 8, "value"), "Identifier");
+    assert_eq!(type_of(&tokens, 9, "type"), "Identifier");
+    assert_eq!(type_of(&tokens, 9, "value"), "Identifier");
+    assert_eq!(type_of(&tokens, 10, "data"), "Identifier");
+}
+
+#[test]
+fn table_column_named_like_a_keyword() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 11, "from"), "Identifier");
+    assert_eq!(type_of(&tokens, 11, "FROM"), "Keyword:Database");
+}
+
+#[test]
+fn method_parameter_names() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 12, "type"), "Identifier");
+    assert_eq!(type_of(&tokens, 12, "value"), "Identifier");
+}
+
+#[test]
+fn inline_declaration_name() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 13, "DATA"), "Keyword:Declaration");
+    assert_eq!(type_of(&tokens, 13, "select"), "Identifier");
+}
+
+#[test]
+fn keyword_in_operand_position_is_not_reclassified() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 14, "type"), "Keyword:Declaration");
+}
+
+#[test]
+fn keywords_match_in_any_case_unless_case_sensitive() {
+    // Reclassification relies on lower-case keywords such as the `type` in
+    // `DATA type TYPE i.` being lexed as keywords first, so patterns follow
+    // `metadata.case_sensitive`.
+    let config = |case_sensitive: bool| {
+        format!(
+            "special_rules = []\n\n[metadata]\nlanguage_version = \"test\"\ncase_sensitive = {}\nallow_unicode_identifiers = false\n\n\
+             [token_categories]\nKeyword = {{ priority = 1, color = \"blue\" }}\nIdentifier = {{ priority = 2, color = \"black\" }}\n\n\
+             [patterns]\nKeyword = [ {{ regex = \"\\\\bDATA\\\\b\" }} ]\nIdentifier = [ {{ regex = \"[a-zA-Z_]+\" }} ]\n\n\
+             [context_rules]\n[custom_actions]\n",
+            case_sensitive
+        )
+    };
+    let tokens = tokenize("data DATA", load_config("case-insensitive", &config(false)));
+    assert_eq!(type_of(&tokens, 1, "data"), "Keyword");
+    assert_eq!(type_of(&tokens, 1, "DATA"), "Keyword");
+
+    let tokens = tokenize("data DATA", load_config("case-sensitive", &config(true)));
+    assert_eq!(type_of(&tokens, 1, "data"), "Identifier");
+    assert_eq!(type_of(&tokens, 1, "DATA"), "Keyword");
+}
@@ -0,0 +1,123 @@
+// abap-tokenizer/tests/declarations.rs
+//! Tests for the type names of declarations, which are recognised by the
+//! `TYPE` and `LIKE` phrases before them, over the corpus in
+//! `tests/corpus/declarations.abap`.
+use abap_tokenizer::config::Dialect;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use std::fs;
+
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus/declarations.abap");
+
+fn corpus_tokens() -> Vec<Token> {
+    let source = fs::read_to_string(CORPUS).unwrap();
+    FlexibleTokenizer::new(&source, Dialect::Abap750.config().unwrap())
+        .tokenize()
+        .unwrap()
+}
+
+fn type_of(tokens: &[Token], line: usize, value: &str) -> String {
+    tokens
+        .iter()
+        .find(|token| token.line == line && token.value == value)
+        .unwrap_or_else(|| panic!("no token {:?} on line {}", value, line))
+        .token_type
+        .to_string()
+}
+
+/// Returns the types and values of the tokens on a line, skipping comments.
+fn line_tokens(tokens: &[Token], line: usize) -> Vec<(String, String)> {
+    tokens
+        .iter()
+        .filter(|token| token.line == line && token.token_type.category != "Comment")
+        .map(|token| (token.token_type.to_string(), token.value.clone()))
+        .collect()
+}
+
+/// Checks that the phrase `phrase` on a line is a keyword followed by the type name `type_name`.
+fn assert_declaration(tokens: &[Token], line: usize, phrase: &str, type_name: &str) {
+    let line_tokens = line_tokens(tokens, line);
+    let position = line_tokens
+        .iter()
+        .position(|(_, value)| value == phrase)
+        .unwrap_or_else(|| panic!("no phrase {:?} on line {}: {:?}", phrase, line, line_tokens));
+    assert_eq!(line_tokens[position].0, "Keyword:Declaration", "line {}", line);
+    assert_eq!(
+        line_tokens[position + 1],
+        ("Identifier:TypeName".to_string(), type_name.to_string()),
+        "line {}",
+        line
+    );
+}
+
+#[test]
+fn type_table_of() {
+    assert_declaration(&corpus_tokens(), 2, "TYPE TABLE OF", "mara");
+}
+
+#[test]
+fn type_table_of_with_table_kind() {
+    let tokens = corpus_tokens();
+    assert_declaration(&tokens, 3, "TYPE SORTED TABLE OF", "mara");
+    assert_declaration(&tokens, 4, "TYPE HASHED TABLE OF", "mara");
+    assert_declaration(&tokens, 5, "TYPE STANDARD TABLE OF", "mara");
+    // The key components are not type names
+    assert_eq!(type_of(&tokens, 3, "matnr"), "Identifier");
+}
+
+#[test]
+fn type_range_of() {
+    assert_declaration(&corpus_tokens(), 6, "TYPE RANGE OF", "matnr");
+}
+
+#[test]
+fn type_line_of() {
+    assert_declaration(&corpus_tokens(), 7, "TYPE LINE OF", "ty_mara_tab");
+}
+
+#[test]
+fn like_forms() {
+    let tokens = corpus_tokens();
+    assert_declaration(&tokens, 8, "LIKE LINE OF", "lt_mara");
+    assert_declaration(&tokens, 9, "LIKE TABLE OF", "ls_mara");
+    assert_declaration(&tokens, 10, "LIKE RANGE OF", "lv_matnr");
+}
+
+#[test]
+fn type_ref_to_and_plain_type() {
+    let tokens = corpus_tokens();
+    assert_declaration(&tokens, 11, "TYPE REF TO", "zcl_demo");
+    assert_eq!(type_of(&tokens, 12, "TYPE"), "Keyword:Declaration");
+    assert_eq!(type_of(&tokens, 12, "matnr"), "Identifier:TypeName");
+}
+
+#[test]
+fn phrase_across_lines_and_comments() {
+    let tokens = corpus_tokens();
+    let phrase = tokens
+        .iter()
+        .find(|token| token.value == "TYPE TABLE OF" && token.line == 13)
+        .unwrap();
+    assert_eq!(phrase.token_type.to_string(), "Keyword:Declaration");
+    assert_eq!(type_of(&tokens, 15, "mara"), "Identifier:TypeName");
+}
+
+#[test]
+fn phrase_in_lower_case() {
+    let tokens = corpus_tokens();
+    assert_declaration(&tokens, 16, "type standard table of", "mara");
+    assert!(tokens
+        .iter()
+        .filter(|token| token.line == 16)
+        .all(|token| token.value != "table" && token.value != "of"));
+}
+
+#[test]
+fn no_table_or_of_tokens() {
+    let stray: Vec<_> = corpus_tokens()
+        .into_iter()
+        .filter(|token| ["TABLE", "OF", "LINE", "RANGE"].contains(&token.value.to_uppercase().as_str()))
+        .map(|token| (token.line, token.value, token.token_type.to_string()))
+        .collect();
+    assert!(stray.is_empty(), "{:?}", stray);
+}
@@ -0,0 +1,108 @@
+// abap-tokenizer/tests/dialects.rs
+//! Tests for combining configurations: the precedence of a file over the
+//! files it imports and the built-in profile it names with `profile`.
+use abap_tokenizer::config::{load_toml_config, TokenizerConfig};
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use abap_tokenizer::ConfigError;
+use std::fs;
+
+const METADATA: &str = "[metadata]\nlanguage_version = \"ABAP 7.40\"\ncase_sensitive = false\nallow_unicode_identifiers = false\n";
+
+const BASE: &str = "[metadata]\nlanguage_version = \"base\"\ncase_sensitive = false\nallow_unicode_identifiers = false\n\n\
+    [token_categories]\nKeyword = { priority = 1, color = \"blue\" }\nIdentifier = { priority = 2, color = \"black\" }\n\n\
+    [patterns]\nKeyword = [ { regex = \"\\\\bWRITE\\\\b\", subcategory = \"Base\" } ]\nIdentifier = [ { regex = \"[a-zA-Z_]+\" } ]\n\n\
+    [context_rules]\nStringLiteral = { start = \"'\", end = \"'\", escape = \"''\" }\nBase = { start = \"[\", end = \"]\" }\n\n\
+    [custom_actions]\nNormalize = { action = \"uppercase\" }\nBase = { action = \"base\" }\n";
+
+/// Writes configuration files into a fresh scratch directory and loads the
+/// first one.
+fn load_configs(test: &str, files: &[(&str, &str)]) -> Result<TokenizerConfig, ConfigError> {
+    let dir = std::env::temp_dir().join(format!("abap-tokenizer-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for (name, content) in files {
+        fs::write(dir.join(name), content).unwrap();
+    }
+    load_toml_config(dir.join(files[0].0).to_str().unwrap())
+}
+
+fn type_of(tokens: &[Token], line: usize, value: &str) -> String {
+    tokens
+        .iter()
+        .find(|token| token.line == line && token.value == value)
+        .unwrap_or_else(|| panic!("no token {:?} on line {}", value, line))
+        .token_type
+        .to_string()
+}
+
+#[test]
+fn importing_file_takes_precedence() {
+    let main = format!(
+        "imports = [\"base.toml\"]\n{}\n\
+         [token_categories]\nKeyword = {{ priority = 1, color = \"red\" }}\n\n\
+         [patterns]\nKeyword = [ {{ regex = \"\\\\bWRITE\\\\b\", subcategory = \"Main\" }} ]\n\n\
+         [context_rules]\nStringLiteral = {{ start = \"`\", end = \"`\" }}\n\n\
+         [custom_actions]\nNormalize = {{ action = \"lowercase\" }}\n",
+        METADATA
+    );
+    let config = load_configs("merge-precedence", &[("main.toml", &main), ("base.toml", BASE)]).unwrap();
+    assert_eq!(config.metadata.language_version, "ABAP 7.40");
+    assert_eq!(config.token_categories["Keyword"].color, "red");
+    assert_eq!(config.context_rules["StringLiteral"].start, "`");
+    assert_eq!(config.custom_actions["Normalize"].action, "lowercase");
+    // Definitions only the import has are added
+    assert_eq!(config.context_rules["Base"].start, "[");
+    assert_eq!(config.custom_actions["Base"].action, "base");
+    assert_eq!(config.token_categories["Identifier"].color, "black");
+    // Patterns of the importing file are tried first
+    let tokens = FlexibleTokenizer::new("WRITE x", config).tokenize().unwrap();
+    assert_eq!(type_of(&tokens, 1, "WRITE"), "Keyword:Main");
+    assert_eq!(type_of(&tokens, 1, "x"), "Identifier");
+}
+
+#[test]
+fn profile_is_merged_after_the_imports() {
+    let main = format!(
+        "imports = [\"base.toml\"]\nprofile = \"7.40\"\n{}\n\
+         [patterns]\nKeyword = [ {{ regex = \"\\\\bDATA\\\\b\", subcategory = \"Main\" }} ]\n",
+        METADATA
+    );
+    let config = load_configs("profile-order", &[("main.toml", &main), ("base.toml", BASE)]).unwrap();
+    let tokens = FlexibleTokenizer::new("DATA(lv_x) = VALUE #( ).\nWRITE lv_x.", config)
+        .tokenize()
+        .unwrap();
+    assert_eq!(type_of(&tokens, 1, "DATA"), "Keyword:Main");
+    assert_eq!(type_of(&tokens, 2, "WRITE"), "Keyword:Base");
+    // Rules only the profile has are added
+    assert_eq!(type_of(&tokens, 1, "VALUE"), "Keyword:ConstructorOperator");
+}
+
+#[test]
+fn profile_accepts_dialect_names_and_language_versions() {
+    for profile in ["7.40", "ABAP 7.40"] {
+        let main = format!("profile = \"{}\"\n{}", profile, METADATA);
+        let config = load_configs("profile-names", &[("main.toml", &main)]).unwrap();
+        let tokens = FlexibleTokenizer::new("DATA(lv_x) = 1.", config).tokenize().unwrap();
+        assert_eq!(type_of(&tokens, 1, "DATA"), "Keyword:Declaration", "{}", profile);
+    }
+}
+
+#[test]
+fn unknown_profile_is_an_error() {
+    let main = format!("profile = \"7.99\"\n{}", METADATA);
+    let result = load_configs("profile-unknown", &[("main.toml", &main)]);
+    assert!(
+        matches!(result, Err(ConfigError::ConfigurationError(ref message)) if message.contains("7.99")),
+        "{:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn profile_is_not_imported_implicitly() {
+    // A file with only metadata gets no rules from its language version
+    let config = load_configs("profile-implicit", &[("main.toml", METADATA)]).unwrap();
+    assert!(config.patterns.is_empty());
+    assert!(config.phrases.is_empty());
+}
@@ -0,0 +1,203 @@
+// abap-tokenizer/tests/encoding.rs
+//! Tests for the decoding of source files: byte order marks, invalid
+//! content, the Windows-1252 table and the mapping of offsets in the decoded
+//! text back to the original bytes, in memory and when reading files.
+use abap_tokenizer::analysis::annotations::annotations_in_file;
+use abap_tokenizer::config::{load_toml_config, TokenizerConfig};
+use abap_tokenizer::encoding::{read_source, Encoding, SourceText};
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use abap_tokenizer::TokenizerError;
+use std::fs;
+use std::path::PathBuf;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+fn config() -> TokenizerConfig {
+    load_toml_config(CONFIG).unwrap()
+}
+
+fn tokenize(source: &str) -> Vec<Token> {
+    FlexibleTokenizer::new(source, config()).tokenize().unwrap()
+}
+
+/// Writes a file into the scratch directory and returns its path.
+fn scratch_file(name: &str, content: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("abap-tokenizer-encoding-{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+fn with_bom(bom: &[u8], content: &[u8]) -> Vec<u8> {
+    [bom, content].concat()
+}
+
+fn utf16le(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+fn utf16be(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_be_bytes).collect()
+}
+
+fn encoding_error(result: Result<SourceText, TokenizerError>) -> String {
+    match result {
+        Err(TokenizerError::EncodingError(message)) => message,
+        other => panic!("expected an encoding error, got {:?}", other),
+    }
+}
+
+/// Checks `original_offset` at every character boundary of the decoded text,
+/// given the width of each character in the original.
+fn assert_offsets(source: &SourceText, bom_length: usize, width: impl Fn(char) -> usize) {
+    let mut original = bom_length;
+    for (offset, character) in source.text.char_indices() {
+        assert_eq!(source.original_offset(offset), original, "at {:?} ({})", character, offset);
+        original += width(character);
+    }
+    assert_eq!(source.original_offset(source.text.len()), original, "at the end");
+}
+
+#[test]
+fn byte_order_mark_takes_precedence_over_the_given_encoding() {
+    let source = SourceText::decode(&with_bom(UTF8_BOM, "Größe".as_bytes()), Some(Encoding::Latin1)).unwrap();
+    assert_eq!((source.text.as_str(), source.encoding, source.bom), ("Größe", Encoding::Utf8, true));
+
+    let source = SourceText::decode(&with_bom(UTF16LE_BOM, &utf16le("año")), Some(Encoding::Utf8)).unwrap();
+    assert_eq!((source.text.as_str(), source.encoding), ("año", Encoding::Utf16Le));
+
+    let source = SourceText::decode(&with_bom(UTF16BE_BOM, &utf16be("año")), Some(Encoding::Windows1252)).unwrap();
+    assert_eq!((source.text.as_str(), source.encoding), ("año", Encoding::Utf16Be));
+}
+
+#[test]
+fn given_encoding_without_byte_order_mark() {
+    let source = SourceText::decode(b"Gr\xF6\xDFe", Some(Encoding::Latin1)).unwrap();
+    assert_eq!((source.text.as_str(), source.encoding, source.bom), ("Größe", Encoding::Latin1, false));
+
+    let source = SourceText::decode(&utf16le("año"), Some(Encoding::Utf16Le)).unwrap();
+    assert_eq!(source.text, "año");
+}
+
+#[test]
+fn utf8_is_the_default() {
+    let source = SourceText::decode("Größe".as_bytes(), None).unwrap();
+    assert_eq!((source.text.as_str(), source.encoding, source.bom), ("Größe", Encoding::Utf8, false));
+    let message = encoding_error(SourceText::decode(b"Gr\xF6\xDFe", None));
+    assert!(message.contains("byte 2"), "{}", message);
+    // Offsets count the byte order mark
+    let message = encoding_error(SourceText::decode(&with_bom(UTF8_BOM, b"Gr\xF6\xDFe"), None));
+    assert!(message.contains("byte 5"), "{}", message);
+}
+
+#[test]
+fn utf16_with_odd_number_of_bytes() {
+    let mut bytes = with_bom(UTF16LE_BOM, &utf16le("ab"));
+    bytes.push(b'c');
+    assert!(encoding_error(SourceText::decode(&bytes, None)).contains("odd number of bytes"));
+
+    let mut bytes = utf16be("ab");
+    bytes.pop();
+    assert!(encoding_error(SourceText::decode(&bytes, Some(Encoding::Utf16Be))).contains("odd number of bytes"));
+
+    // A byte order mark alone is an empty text
+    let source = SourceText::decode(UTF16LE_BOM, None).unwrap();
+    assert_eq!(source.text, "");
+}
+
+#[test]
+fn utf16_with_unpaired_surrogate() {
+    let bytes = with_bom(UTF16LE_BOM, &[0x61, 0x00, 0x00, 0xD8, 0x62, 0x00]);
+    let message = encoding_error(SourceText::decode(&bytes, None));
+    assert!(message.contains("0xd800"), "{}", message);
+}
+
+#[test]
+fn windows_1252_defined_bytes() {
+    let source = SourceText::decode(b"\x80 \x8A \x93x\x94 \x96 \x99 \x9F", Some(Encoding::Windows1252)).unwrap();
+    assert_eq!(source.text, "€ Š “x” – ™ Ÿ");
+    // The same bytes are C1 controls in ISO-8859-1
+    let source = SourceText::decode(b"\x80\x9F", Some(Encoding::Latin1)).unwrap();
+    assert_eq!(source.text, "\u{80}\u{9F}");
+}
+
+#[test]
+fn windows_1252_undefined_bytes_map_to_c1_controls() {
+    let source = SourceText::decode(b"\x81\x8D\x8F\x90\x9D", Some(Encoding::Windows1252)).unwrap();
+    assert_eq!(source.text, "\u{81}\u{8D}\u{8F}\u{90}\u{9D}");
+    assert_offsets(&source, 0, |_| 1);
+}
+
+#[test]
+fn offsets_in_single_byte_encodings() {
+    let source = SourceText::decode(b"DATA gr\xF6\xDFe TYPE i. \" \x80\n", Some(Encoding::Windows1252)).unwrap();
+    assert_eq!(source.text, "DATA größe TYPE i. \" €\n");
+    assert_offsets(&source, 0, |_| 1);
+}
+
+#[test]
+fn offsets_in_utf16() {
+    // Characters outside the BMP take a surrogate pair
+    let text = "WRITE 'ñ'. \" 😀 ok\n";
+    let source = SourceText::decode(&with_bom(UTF16LE_BOM, &utf16le(text)), None).unwrap();
+    assert_eq!(source.text, text);
+    assert_offsets(&source, UTF16LE_BOM.len(), |character| 2 * character.len_utf16());
+
+    let source = SourceText::decode(&utf16be(text), Some(Encoding::Utf16Be)).unwrap();
+    assert_offsets(&source, 0, |character| 2 * character.len_utf16());
+}
+
+#[test]
+fn offsets_in_utf8_are_shifted_by_the_byte_order_mark() {
+    let source = SourceText::decode(&with_bom(UTF8_BOM, "año €".as_bytes()), None).unwrap();
+    assert_offsets(&source, UTF8_BOM.len(), char::len_utf8);
+
+    let source = SourceText::decode("año €".as_bytes(), None).unwrap();
+    assert_offsets(&source, 0, char::len_utf8);
+}
+
+#[test]
+fn token_spans_map_back_to_the_original_bytes() {
+    let original: &[u8] = b"DATA gr\xF6\xDFe TYPE string VALUE 'Ma\xDFe'.\n";
+    let source = SourceText::decode(original, Some(Encoding::Latin1)).unwrap();
+    let tokens = tokenize(&source.text);
+    for token in &tokens {
+        let span = source.original_span(token.span);
+        let expected = SourceText::decode(&original[span.start..span.end], Some(Encoding::Latin1)).unwrap();
+        assert_eq!(expected.text, token.value, "{:?}", span);
+    }
+    let literal = tokens.iter().find(|token| token.value == "'Maße'").unwrap();
+    let span = source.original_span(literal.span);
+    assert_eq!(&original[span.start..span.end], b"'Ma\xDFe'");
+}
+
+#[test]
+fn read_source_uses_the_given_encoding_and_names_the_file() {
+    let path = scratch_file("latin1.abap", b"WRITE 'a\xF1o'.\n");
+    let source = read_source(&path, Some(Encoding::Latin1)).unwrap();
+    assert_eq!(source.text, "WRITE 'año'.\n");
+
+    let message = encoding_error(read_source(&path, None));
+    assert!(message.contains("latin1.abap"), "{}", message);
+    assert!(matches!(
+        read_source(&path.with_extension("missing"), None),
+        Err(TokenizerError::IoError(_))
+    ));
+}
+
+#[test]
+fn annotation_spans_are_offsets_in_the_original_file() {
+    let original: &[u8] = b"DATA gr\xF6\xDFe TYPE i ##NEEDED.\n";
+    let path = scratch_file("annotations.abap", original);
+    let mut config = config();
+    config.metadata.encoding = Some(Encoding::Latin1);
+    let file = annotations_in_file(path.to_str().unwrap(), config).unwrap();
+    let statement = &file.statements[0];
+    assert_eq!(&original[statement.span.start..statement.span.end], &original[..original.len() - 1]);
+    let pragma = statement.annotations[0].span;
+    assert_eq!(&original[pragma.start..pragma.end], b"##NEEDED");
+}
@@ -0,0 +1,134 @@
+// abap-tokenizer/tests/includes.rs
+//! Tests for the resolution of `INCLUDE` statements: inlined tokens and
+//! their source map, missing includes and cyclic includes.
+use abap_tokenizer::config::{load_toml_config, TokenizerConfig};
+use abap_tokenizer::preprocess::includes::{IncludeResolver, ResolvedProgram};
+use abap_tokenizer::Severity;
+use std::fs;
+use std::path::PathBuf;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+fn config() -> TokenizerConfig {
+    load_toml_config(CONFIG).unwrap()
+}
+
+/// Writes source files into a fresh scratch directory and returns its path.
+fn source_root(test: &str, files: &[(&str, &str)]) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("abap-tokenizer-includes-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for (name, content) in files {
+        let path = root.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    root
+}
+
+/// Resolves the first file of a scratch source root.
+fn resolve(test: &str, files: &[(&str, &str)]) -> (PathBuf, ResolvedProgram) {
+    let root = source_root(test, files);
+    let program = IncludeResolver::new(&root, config())
+        .unwrap()
+        .resolve(&root.join(files[0].0))
+        .unwrap();
+    (root, program)
+}
+
+fn values(program: &ResolvedProgram) -> Vec<&str> {
+    program.tokens.iter().map(|token| token.value.as_str()).collect()
+}
+
+#[test]
+fn resolved_include_is_inlined() {
+    let (root, program) = resolve(
+        "resolved",
+        &[
+            ("zmain.prog.abap", "REPORT zmain.\nINCLUDE zmain_top.\nWRITE lv_count.\n"),
+            ("includes/zmain_top.prog.abap", "\" Global data\nDATA lv_count TYPE i.\n"),
+        ],
+    );
+    assert!(program.diagnostics.is_empty(), "{:?}", program.diagnostics);
+    assert_eq!(
+        values(&program),
+        ["REPORT", "zmain", ".", "\" Global data\n", "DATA", "lv_count", "TYPE", "i", ".", "WRITE", "lv_count", "."]
+    );
+
+    let files = &program.source_map.files;
+    assert_eq!(files.len(), 2);
+    assert_eq!(files[0].name, "ZMAIN");
+    assert_eq!(files[1].name, "ZMAIN_TOP");
+    assert_eq!(files[1].path, root.join("includes/zmain_top.prog.abap"));
+
+    // Inlined tokens carry the id of their file and spans relative to it
+    let data = &program.tokens[4];
+    assert_eq!(data.file_id, 1);
+    assert_eq!((data.line, data.column), (2, 1));
+    assert_eq!(program.source_map.text(data), Some("DATA"));
+    assert_eq!(program.source_map.file(data.file_id).unwrap().name, "ZMAIN_TOP");
+    let write = &program.tokens[9];
+    assert_eq!(write.file_id, 0);
+    assert_eq!(program.source_map.text(write), Some("WRITE"));
+}
+
+#[test]
+fn nested_includes_and_namespaced_names() {
+    let (_, program) = resolve(
+        "nested",
+        &[
+            ("zmain.prog.abap", "INCLUDE /abc/top.\n"),
+            ("#abc#top.prog.abap", "INCLUDE zdeep.\n"),
+            ("zdeep.abap", "CLEAR x.\n"),
+        ],
+    );
+    assert!(program.diagnostics.is_empty(), "{:?}", program.diagnostics);
+    assert_eq!(values(&program), ["CLEAR", "x", "."]);
+    let names: Vec<&str> = program.source_map.files.iter().map(|file| file.name.as_str()).collect();
+    assert_eq!(names, ["ZMAIN", "/ABC/TOP", "ZDEEP"]);
+    assert_eq!(program.tokens[0].file_id, 2);
+}
+
+#[test]
+fn missing_include_is_reported() {
+    let (_, program) = resolve(
+        "missing",
+        &[("zmain.prog.abap", "REPORT zmain.\n  INCLUDE zmissing.\nINCLUDE zoptional IF FOUND.\n")],
+    );
+    // The optional include is skipped silently
+    assert_eq!(program.diagnostics.len(), 1);
+    let diagnostic = &program.diagnostics[0];
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(diagnostic.message, "include ZMISSING not found");
+    assert_eq!((diagnostic.line, diagnostic.column, diagnostic.file_id), (2, 3, 0));
+    // The unresolved INCLUDE statements are dropped
+    assert_eq!(values(&program), ["REPORT", "zmain", "."]);
+}
+
+#[test]
+fn cyclic_include_is_reported() {
+    let (_, program) = resolve(
+        "cyclic",
+        &[
+            ("zmain.prog.abap", "INCLUDE za.\n"),
+            ("za.prog.abap", "WRITE 'a'.\nINCLUDE zb.\n"),
+            ("zb.prog.abap", "WRITE 'b'.\nINCLUDE za.\n"),
+        ],
+    );
+    assert_eq!(program.diagnostics.len(), 1);
+    let diagnostic = &program.diagnostics[0];
+    assert_eq!(diagnostic.message, "recursive INCLUDE of ZA");
+    // Reported in zb, where the cycle closes
+    assert_eq!(program.source_map.file(diagnostic.file_id).unwrap().name, "ZB");
+    assert_eq!((diagnostic.line, diagnostic.column), (2, 1));
+    assert_eq!(values(&program), ["WRITE", "'a'", ".", "WRITE", "'b'", "."]);
+}
+
+#[test]
+fn structure_includes_are_not_resolved() {
+    let (_, program) = resolve(
+        "structure",
+        &[("zmain.prog.abap", "TYPES BEGIN OF ty.\nINCLUDE TYPE zs_base.\nTYPES END OF ty.\n")],
+    );
+    assert!(program.diagnostics.is_empty());
+    assert!(values(&program).contains(&"INCLUDE"));
+}
@@ -0,0 +1,102 @@
+// abap-tokenizer/tests/literals.rs
+//! Tests for typed literals: the subcategories assigned by the default
+//! configuration, `literals::decode` and `literals::join_literal_concatenations`.
+use abap_tokenizer::config::load_toml_config;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::literals::{decode, join_literal_concatenations};
+use abap_tokenizer::tokenizer::token::Token;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+fn tokenize(source: &str) -> Vec<Token> {
+    FlexibleTokenizer::new(source, load_toml_config(CONFIG).unwrap())
+        .tokenize()
+        .unwrap()
+}
+
+/// Returns the literal tokens of a source.
+fn literals(source: &str) -> Vec<Token> {
+    tokenize(source)
+        .into_iter()
+        .filter(|token| token.token_type.category == "Literal")
+        .collect()
+}
+
+#[test]
+fn doubled_quotes_are_unescaped() {
+    let literals = literals("lv_text = 'It''s'.");
+    assert_eq!(literals.len(), 1);
+    assert_eq!(literals[0].value, "'It''s'");
+    assert_eq!(literals[0].token_type.to_string(), "Literal:Text");
+    assert_eq!(literals[0].decoded_value.as_deref(), Some("It's"));
+    assert_eq!(decode(&literals[0]).as_deref(), Some("It's"));
+
+    // An empty literal and one made only of an escaped quote
+    let literals = self::literals("WRITE: '', ''''.");
+    assert_eq!(literals[0].decoded_value.as_deref(), Some(""));
+    assert_eq!(literals[1].decoded_value.as_deref(), Some("'"));
+}
+
+#[test]
+fn backquote_strings() {
+    let literals = literals("lv_string = `say ``hi`` 'now'`.");
+    assert_eq!(literals.len(), 1);
+    assert_eq!(literals[0].token_type.to_string(), "Literal:String");
+    assert_eq!(literals[0].decoded_value.as_deref(), Some("say `hi` 'now'"));
+}
+
+#[test]
+fn quoted_numbers_and_text() {
+    let literals = literals("WRITE: '12', '-12.5E3', '1.2.3', 'A1', 42.");
+    let types: Vec<String> = literals.iter().map(|token| token.token_type.to_string()).collect();
+    assert_eq!(
+        types,
+        ["Literal:QuotedNumber", "Literal:QuotedNumber", "Literal:Text", "Literal:Text", "Literal:Integer"]
+    );
+    assert_eq!(literals[1].decoded_value.as_deref(), Some("-12.5E3"));
+    assert_eq!(literals[4].decoded_value.as_deref(), Some("42"));
+}
+
+#[test]
+fn text_symbol_literals_decode_without_the_key() {
+    let literals = literals("WRITE 'Don''t'(001).");
+    assert_eq!(literals[0].token_type.to_string(), "Literal:TextSymbol");
+    assert_eq!(literals[0].decoded_value.as_deref(), Some("Don't"));
+}
+
+#[test]
+fn non_literals_are_not_decoded() {
+    let tokens = tokenize("WRITE lv_x.");
+    assert!(tokens.iter().all(|token| decode(token).is_none()));
+}
+
+#[test]
+fn concatenation_joined_across_lines() {
+    let source = "lv_text = 'abc' &\n          'd''e' &\n          'f'.";
+    let tokens = join_literal_concatenations(tokenize(source));
+    let values: Vec<&str> = tokens.iter().map(|token| token.value.as_str()).collect();
+    assert_eq!(values, ["lv_text", "=", "'abc' & 'd''e' & 'f'", "."]);
+    assert_eq!(tokens[2].decoded_value.as_deref(), Some("abcd'ef"));
+    assert_eq!(tokens[2].token_type.to_string(), "Literal:Text");
+    assert_eq!((tokens[2].line, tokens[2].column), (1, 11));
+    // The span covers the whole chain, including the line breaks
+    assert_eq!(&source[tokens[2].span.start..tokens[2].span.end], "'abc' &\n          'd''e' &\n          'f'");
+}
+
+#[test]
+fn only_literals_of_the_same_kind_are_joined() {
+    let tokens = join_literal_concatenations(tokenize("x = 'a' & `b`. y = `c` & `d`. z = 'e' & lv_f."));
+    let values: Vec<&str> = tokens.iter().map(|token| token.value.as_str()).collect();
+    assert_eq!(
+        values,
+        ["x", "=", "'a'", "&", "`b`", ".", "y", "=", "`c` & `d`", ".", "z", "=", "'e'", "&", "lv_f", "."]
+    );
+    assert_eq!(tokens[8].decoded_value.as_deref(), Some("cd"));
+}
+
+#[test]
+fn string_concatenation_operator_is_not_a_join() {
+    let tokens = join_literal_concatenations(tokenize("x = `a` && `b`."));
+    assert_eq!(tokens[3].token_type.to_string(), "Operator:Concatenation");
+    assert_eq!(tokens.len(), 6);
+}
@@ -0,0 +1,126 @@
+// abap-tokenizer/tests/macros.rs
+//! Tests for macro expansion: placeholders, chained and nested invocations,
+//! and the diagnostics for recursive, unterminated and undefined macros.
+use abap_tokenizer::config::load_toml_config;
+use abap_tokenizer::preprocess::macros::{expand_macros, MacroExpansion};
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::Severity;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+fn expand(source: &str) -> MacroExpansion {
+    let tokens = FlexibleTokenizer::new(source, load_toml_config(CONFIG).unwrap())
+        .tokenize()
+        .unwrap();
+    expand_macros(tokens)
+}
+
+/// Returns the expanded source as token values separated by spaces.
+fn text(expansion: &MacroExpansion) -> String {
+    expansion
+        .tokens
+        .iter()
+        .map(|token| token.value.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[test]
+fn placeholders_are_substituted() {
+    let expansion = expand(
+        "DEFINE all_args.\n  WRITE: &1, &2, &3, &4, &5, &6, &7, &8, &9.\nEND-OF-DEFINITION.\n\
+         all_args a b c d e f g h i.\n",
+    );
+    assert!(expansion.diagnostics.is_empty(), "{:?}", expansion.diagnostics);
+    assert_eq!(text(&expansion), "WRITE : a , b , c , d , e , f , g , h , i .");
+
+    let definition = &expansion.definitions[0];
+    assert_eq!(definition.name, "ALL_ARGS");
+    assert_eq!((definition.line, definition.column), (1, 1));
+    // The body keeps the placeholders
+    assert!(definition.body.iter().any(|token| token.value == "&9"));
+}
+
+#[test]
+fn adjacent_tokens_form_one_argument() {
+    let expansion = expand("DEFINE move.\n  &2 = &1.\nEND-OF-DEFINITION.\nmove ls_a-field lo_b->attr.\n");
+    assert_eq!(text(&expansion), "lo_b -> attr = ls_a - field .");
+}
+
+#[test]
+fn missing_arguments_are_reported() {
+    let expansion = expand("DEFINE move.\n  &2 = &1.\nEND-OF-DEFINITION.\nmove lv_a.\n");
+    assert_eq!(expansion.diagnostics.len(), 1);
+    assert_eq!(expansion.diagnostics[0].severity, Severity::Warning);
+    assert_eq!((expansion.diagnostics[0].line, expansion.diagnostics[0].column), (4, 1));
+}
+
+#[test]
+fn chained_invocation_expands_each_link() {
+    let source = "DEFINE add.\n  &1 = &1 + &2.\nEND-OF-DEFINITION.\nadd: lv_a 1, lv_b 2.\n";
+    let expansion = expand(source);
+    assert_eq!(text(&expansion), "lv_a = lv_a + 1 . lv_b = lv_b + 2 .");
+
+    // Every expanded token points to the invocation
+    let origin = expansion.tokens[0].macro_origin.as_ref().unwrap();
+    assert_eq!(origin.macro_name, "ADD");
+    assert_eq!((origin.call_line, origin.call_column), (4, 1));
+    assert_eq!(&source[origin.call_span.start..origin.call_span.end], "add: lv_a 1, lv_b 2.");
+    assert!(expansion.tokens.iter().all(|token| token.macro_origin.as_ref() == Some(origin)));
+    // Argument tokens keep their position in the invocation
+    assert_eq!((expansion.tokens[0].line, expansion.tokens[0].column), (4, 6));
+}
+
+#[test]
+fn nested_macro_call() {
+    let expansion = expand(
+        "DEFINE inner.\n  WRITE &1.\nEND-OF-DEFINITION.\n\
+         DEFINE outer.\n  inner &1.\n  inner &2.\nEND-OF-DEFINITION.\n\
+         outer lv_a lv_b.\n",
+    );
+    assert!(expansion.diagnostics.is_empty(), "{:?}", expansion.diagnostics);
+    assert_eq!(text(&expansion), "WRITE lv_a . WRITE lv_b .");
+    // The tokens of the nested expansion record the innermost invocation
+    let origin = expansion.tokens[0].macro_origin.as_ref().unwrap();
+    assert_eq!(origin.macro_name, "INNER");
+    assert_eq!(origin.call_line, 5);
+}
+
+#[test]
+fn recursive_macro_is_reported() {
+    let expansion = expand("DEFINE loop_forever.\n  loop_forever.\nEND-OF-DEFINITION.\nloop_forever.\n");
+    assert_eq!(expansion.diagnostics.len(), 1);
+    assert_eq!(expansion.diagnostics[0].severity, Severity::Error);
+    assert_eq!(
+        expansion.diagnostics[0].message,
+        "macro LOOP_FOREVER is expanded recursively"
+    );
+    // The invocation at the depth limit is kept unexpanded
+    assert_eq!(text(&expansion), "loop_forever .");
+}
+
+#[test]
+fn missing_end_of_definition() {
+    let expansion = expand("WRITE lv_a.\nDEFINE open.\n  WRITE &1.\n");
+    assert_eq!(expansion.diagnostics.len(), 1);
+    assert_eq!(expansion.diagnostics[0].message, "missing END-OF-DEFINITION for macro open");
+    assert_eq!((expansion.diagnostics[0].line, expansion.diagnostics[0].column), (2, 1));
+    // The rest of the source is dropped with the definition
+    assert_eq!(text(&expansion), "WRITE lv_a .");
+    assert!(expansion.definitions.is_empty());
+}
+
+#[test]
+fn macro_used_before_its_definition_is_not_expanded() {
+    let expansion = expand("greet lv_a.\nDEFINE greet.\n  WRITE &1.\nEND-OF-DEFINITION.\ngreet lv_b.\n");
+    assert!(expansion.diagnostics.is_empty());
+    assert_eq!(text(&expansion), "greet lv_a . WRITE lv_b .");
+    assert!(expansion.tokens[0].macro_origin.is_none());
+}
+
+#[test]
+fn comments_are_kept_outside_and_dropped_inside_definitions() {
+    let expansion = expand("DEFINE m.\n  \" body comment\n  WRITE &1.\nEND-OF-DEFINITION.\n* call\nm x.\n");
+    assert!(expansion.definitions[0].body.iter().all(|token| !token.is_comment()));
+    assert_eq!(text(&expansion), "* call\n WRITE x .");
+}
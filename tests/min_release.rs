@@ -0,0 +1,120 @@
+// abap-tokenizer/tests/min_release.rs
+//! Tests for the detection of the minimum ABAP release: one fixture per
+//! release-specific feature, the release it pins and the usages reported by
+//! `FileRelease::pinning`.
+use abap_tokenizer::analysis::min_release::{
+    min_release_in_file, Feature, FileRelease, Release,
+};
+use abap_tokenizer::config::Dialect;
+use std::fs;
+
+/// Writes a source into a scratch file and determines its minimum release
+/// with the newest profile.
+fn report(test: &str, source: &str) -> FileRelease {
+    let path = std::env::temp_dir().join(format!("abap-tokenizer-min-release-{}-{}.abap", test, std::process::id()));
+    fs::write(&path, source).unwrap();
+    min_release_in_file(path.to_str().unwrap(), Dialect::Abap75x.config().unwrap()).unwrap()
+}
+
+/// Returns the features and texts of the usages that pin the minimum release.
+fn pinning(report: &FileRelease) -> Vec<(Feature, String)> {
+    report
+        .pinning()
+        .map(|usage| (usage.feature, usage.text.clone()))
+        .collect()
+}
+
+#[test]
+fn each_feature_pins_its_release() {
+    let fixtures = [
+        ("pragma", "DATA lv_x TYPE i ##NEEDED.", Feature::Pragma, "##NEEDED"),
+        ("template", "lv_s = |Hello|.", Feature::StringTemplate, "|"),
+        ("concatenation", "lv_s = lv_a && lv_b.", Feature::Concatenation, "&&"),
+        ("inline-data", "DATA(lv_x) = 1.", Feature::InlineDeclaration, "DATA"),
+        ("new", "lo_app = NEW lcl_app( ).", Feature::ConstructorExpression, "NEW"),
+        ("value", "lt_tab = VALUE #( ( 1 ) ).", Feature::ConstructorExpression, "VALUE"),
+        ("table-expression", "lv_x = lt_tab[ 1 ].", Feature::TableExpression, "["),
+        ("host-variable", "SELECT * FROM sflight INTO TABLE @lt_f.", Feature::HostVariable, "@"),
+        ("corresponding", "ls_b = CORRESPONDING #( ls_a ).", Feature::Corresponding, "CORRESPONDING"),
+        ("reduce", "lv_n = REDUCE i( INIT s = 0 FOR x IN lt_tab NEXT s = s + x ).", Feature::IterationExpression, "REDUCE"),
+        ("host-inline", "SELECT * FROM sflight INTO TABLE @DATA(lt_f).", Feature::HostInlineDeclaration, "@"),
+        ("host-expression", "SELECT * FROM sflight WHERE price > @( lv_min * 2 ) INTO TABLE @lt_f.", Feature::HostExpression, "@"),
+        ("instance-of", "IF lo_obj IS INSTANCE OF zcl_x.\nENDIF.", Feature::TypePredicate, "IS INSTANCE OF"),
+        ("enum", "TYPES BEGIN OF ENUM ty_color.\nTYPES red.\nTYPES END OF ENUM ty_color.", Feature::Enumeration, "BEGIN OF ENUM"),
+        ("final", "FINAL(lv_x) = 1.", Feature::FinalDeclaration, "FINAL"),
+    ];
+    for (test, source, feature, text) in fixtures {
+        let report = report(test, source);
+        assert_eq!(report.minimum, Some(feature.release()), "{}", test);
+        assert_eq!(pinning(&report), [(feature, text.to_string())], "{}", test);
+    }
+}
+
+#[test]
+fn releases_of_features() {
+    assert_eq!(Feature::Pragma.release(), Release::new(7, 2));
+    assert_eq!(Feature::InlineDeclaration.release(), Release::with_support_package(7, 40, 2));
+    assert_eq!(Feature::HostVariable.release(), Release::with_support_package(7, 40, 5));
+    assert_eq!(Feature::HostInlineDeclaration.release(), Release::with_support_package(7, 40, 8));
+    assert_eq!(Feature::HostExpression.release(), Release::new(7, 50));
+    assert_eq!(Feature::Enumeration.release(), Release::new(7, 51));
+    assert_eq!(Feature::FinalDeclaration.release(), Release::new(7, 57));
+    assert_eq!(Release::with_support_package(7, 40, 8).to_string(), "7.40 SP08");
+    assert_eq!(Release::new(7, 2).to_string(), "7.02");
+}
+
+#[test]
+fn field_symbol_inline_declaration() {
+    let report = report("field-symbol", "LOOP AT lt_tab ASSIGNING FIELD-SYMBOL(<ls_row>).\nENDLOOP.");
+    assert_eq!(report.minimum, Some(Feature::InlineDeclaration.release()));
+    assert_eq!(pinning(&report)[0].0, Feature::InlineDeclaration);
+}
+
+#[test]
+fn sql_functions_only_in_open_sql() {
+    let report_740 = report("sql-function", "SELECT COALESCE( price, 0 ) FROM sflight INTO TABLE @lt_f.");
+    assert_eq!(report_740.minimum, Some(Release::with_support_package(7, 40, 8)));
+    assert_eq!(pinning(&report_740), [(Feature::SqlFunction, "COALESCE".to_string())]);
+
+    let report_750 = report("sql-function-750", "SELECT UPPER( carrid ) FROM sflight INTO TABLE @lt_f.");
+    assert_eq!(report_750.minimum, Some(Release::new(7, 50)));
+    assert_eq!(pinning(&report_750), [(Feature::SqlFunction750, "UPPER".to_string())]);
+
+    // Outside Open SQL, a call of a method named like a function is ABAP
+    let report_abap = report("sql-function-abap", "lv_x = upper( lv_y ).");
+    assert_eq!(report_abap.minimum, None);
+}
+
+#[test]
+fn amdp_method() {
+    let report = report(
+        "amdp",
+        "METHOD get BY DATABASE PROCEDURE FOR HDB LANGUAGE SQLSCRIPT.\n  SELECT * FROM sflight;\nENDMETHOD.",
+    );
+    assert_eq!(report.minimum, Some(Feature::Amdp.release()));
+    assert_eq!(pinning(&report)[0].0, Feature::Amdp);
+}
+
+#[test]
+fn newest_feature_pins_the_release() {
+    let source = "DATA lv_x TYPE i ##NEEDED.\nDATA(lv_y) = lv_x.\nlt_a = VALUE #( ).\nlv_s = |{ lv_y }|.";
+    let report = report("mixed", source);
+    assert_eq!(report.minimum, Some(Release::with_support_package(7, 40, 2)));
+    let lines: Vec<_> = report.pinning().map(|usage| (usage.line, usage.column)).collect();
+    assert_eq!(lines, [(2, 1), (3, 8)]);
+    assert_eq!(report.usages.len(), 4);
+}
+
+#[test]
+fn classic_source_needs_no_release() {
+    let report = report("classic", "* Classic\nDATA lv_x TYPE i.\nWRITE lv_x.\nlv_s = 'a'.");
+    assert_eq!(report.minimum, None);
+    assert!(report.usages.is_empty());
+    assert_eq!(report.pinning().count(), 0);
+}
+
+#[test]
+fn comments_and_literals_are_not_features() {
+    let report = report("comments", "\" DATA(lv_x) = NEW lcl( ).\nlv_s = 'DATA(x)'.");
+    assert_eq!(report.minimum, None);
+}
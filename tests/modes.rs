@@ -0,0 +1,214 @@
+// abap-tokenizer/tests/modes.rs
+//! Tests for lexer modes: `push` and `pop` transitions, nested modes, modes
+//! left open at the end of input, native SQL blocks and the loading of mode
+//! configs from separate files.
+use abap_tokenizer::config::{load_toml_config, TokenizerConfig};
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::ConfigError;
+use std::fs;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+const METADATA: &str = "[metadata]\nlanguage_version = \"test\"\ncase_sensitive = false\nallow_unicode_identifiers = false\n";
+
+/// Required sections of a configuration without rules of its own.
+const EMPTY_SECTIONS: &str = "special_rules = []\n\n[token_categories]\n\n[patterns]\n\n[context_rules]\n\n[custom_actions]\n";
+
+/// Configuration with a `sql` mode, entered after `SQL` and left at `ENDSQL`,
+/// and a `paren` mode nested in it between parentheses.
+const MODES: &str = r#"
+special_rules = []
+
+[metadata]
+language_version = "test"
+case_sensitive = true
+allow_unicode_identifiers = false
+
+[token_categories]
+Keyword = { priority = 1, color = "blue" }
+Identifier = { priority = 2, color = "black" }
+Punctuation = { priority = 3, color = "gray" }
+
+[patterns]
+Keyword = [ { regex = "\\bSQL\\b", push = "sql" }, { regex = "\\bDATA\\b" } ]
+Identifier = [ { regex = "[A-Za-z_]+" } ]
+Punctuation = [ { regex = "[.]" } ]
+
+[context_rules]
+
+[custom_actions]
+
+[modes.sql.token_categories]
+SqlEnd = { priority = 1, color = "blue" }
+SqlWord = { priority = 2, color = "black" }
+SqlOpen = { priority = 3, color = "gray" }
+
+[modes.sql.patterns]
+SqlEnd = [ { regex = "\\bENDSQL\\b", pop = true } ]
+SqlWord = [ { regex = "[A-Za-z_]+" } ]
+SqlOpen = [ { regex = "\\(", push = "paren" } ]
+
+[modes.paren.token_categories]
+ParenWord = { priority = 1, color = "black" }
+ParenClose = { priority = 2, color = "gray" }
+
+[modes.paren.patterns]
+ParenWord = [ { regex = "[A-Za-z_]+" } ]
+ParenClose = [ { regex = "\\)", pop = true } ]
+"#;
+
+/// Writes configuration files into a fresh scratch directory and loads the
+/// first one.
+fn load_configs(test: &str, files: &[(&str, &str)]) -> Result<TokenizerConfig, ConfigError> {
+    let dir = std::env::temp_dir().join(format!("abap-tokenizer-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for (name, content) in files {
+        fs::write(dir.join(name), content).unwrap();
+    }
+    load_toml_config(dir.join(files[0].0).to_str().unwrap())
+}
+
+fn config() -> TokenizerConfig {
+    let path = std::env::temp_dir().join(format!("abap-tokenizer-modes-{}.toml", std::process::id()));
+    fs::write(&path, MODES).unwrap();
+    load_toml_config(path.to_str().unwrap()).unwrap()
+}
+
+/// Tokenizes a source, returning `value category mode` triples and the mode
+/// active at the end of input.
+fn tokenize(source: &str) -> (Vec<String>, String) {
+    tokenize_with(source, config())
+}
+
+fn tokenize_with(source: &str, config: TokenizerConfig) -> (Vec<String>, String) {
+    let mut tokenizer = FlexibleTokenizer::new(source, config);
+    let tokens = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| format!("{} {} {}", token.value, token.token_type.category, token.mode))
+        .collect();
+    (tokens, tokenizer.current_mode().to_string())
+}
+
+#[test]
+fn push_and_pop() {
+    let (tokens, mode) = tokenize("SQL select x ENDSQL DATA");
+    assert_eq!(
+        tokens,
+        [
+            "SQL Keyword main",
+            "select SqlWord sql",
+            "x SqlWord sql",
+            "ENDSQL SqlEnd sql",
+            "DATA Keyword main",
+        ]
+    );
+    assert_eq!(mode, "main");
+}
+
+#[test]
+fn nested_modes() {
+    let (tokens, mode) = tokenize("SQL a ( b ) c ENDSQL.");
+    assert_eq!(
+        tokens,
+        [
+            "SQL Keyword main",
+            "a SqlWord sql",
+            "( SqlOpen sql",
+            "b ParenWord paren",
+            ") ParenClose paren",
+            "c SqlWord sql",
+            "ENDSQL SqlEnd sql",
+            ". Punctuation main",
+        ]
+    );
+    assert_eq!(mode, "main");
+}
+
+#[test]
+fn mode_left_open_at_end_of_input() {
+    let (tokens, mode) = tokenize("SQL a ( b");
+    assert_eq!(tokens.last().unwrap(), "b ParenWord paren");
+    assert_eq!(mode, "paren");
+}
+
+#[test]
+fn push_to_an_undefined_mode_is_an_error() {
+    let path = std::env::temp_dir().join(format!("abap-tokenizer-modes-undefined-{}.toml", std::process::id()));
+    fs::write(&path, MODES.replace("push = \"paren\"", "push = \"missing\"")).unwrap();
+    assert!(load_toml_config(path.to_str().unwrap()).is_err());
+}
+
+#[test]
+fn native_sql_block_is_lexed_in_its_mode() {
+    let (tokens, mode) = tokenize_with(
+        "EXEC SQL.\n  SELECT \"name\" FROM t WHERE id = :lv_id\nENDEXEC.\nWRITE x.\n",
+        load_toml_config(CONFIG).unwrap(),
+    );
+    // `push_after` enters the mode after the `.` closing `EXEC SQL`
+    assert_eq!(tokens[0], "EXEC SQL Keyword main");
+    assert_eq!(tokens[1], ". Punctuation main");
+    assert_eq!(tokens[2], "SELECT Keyword native_sql");
+    assert!(tokens.contains(&"\"name\" Identifier native_sql".to_string()));
+    assert!(tokens.contains(&":lv_id HostVariable native_sql".to_string()));
+    // `end` leaves the mode without consuming `ENDEXEC`
+    let end = tokens.iter().position(|token| token == "ENDEXEC Keyword main").unwrap();
+    assert!(tokens[end..].iter().all(|token| token.ends_with(" main")));
+    assert_eq!(mode, "main");
+}
+
+#[test]
+fn mode_end_is_only_checked_at_the_next_token() {
+    // `ENDEXEC` later on the line does not end the mode early
+    let (tokens, _) = tokenize_with(
+        "EXEC SQL. SELECT a FROM t ENDEXEC.",
+        load_toml_config(CONFIG).unwrap(),
+    );
+    assert_eq!(tokens[3], "a Identifier native_sql");
+    assert_eq!(tokens[5], "t Identifier native_sql");
+    assert_eq!(tokens[6], "ENDEXEC Keyword main");
+}
+
+#[test]
+fn native_sql_block_left_open_at_end_of_input() {
+    let (tokens, mode) = tokenize_with("EXEC SQL.\n  SELECT a FROM t\n", load_toml_config(CONFIG).unwrap());
+    assert_eq!(tokens.last().unwrap(), "t Identifier native_sql");
+    assert_eq!(mode, "native_sql");
+}
+
+#[test]
+fn mode_config_referring_to_itself_is_an_error() {
+    let config = format!("{}{}\n[modes.inner]\nconfig = \"self.toml\"\n", EMPTY_SECTIONS, METADATA);
+    let result = load_configs("mode-self", &[("self.toml", &config)]);
+    assert!(
+        matches!(result, Err(ConfigError::ConfigurationError(ref message)) if message.contains("Recursive")),
+        "{:?}",
+        result.err()
+    );
+}
+
+#[test]
+fn mode_configs_referring_to_each_other_are_an_error() {
+    let first = format!("{}{}\n[modes.second]\nconfig = \"second.toml\"\n", EMPTY_SECTIONS, METADATA);
+    let second = format!("{}{}\n[modes.first]\nconfig = \"first.toml\"\n", EMPTY_SECTIONS, METADATA);
+    let result = load_configs("mode-mutual", &[("first.toml", &first), ("second.toml", &second)]);
+    assert!(matches!(result, Err(ConfigError::ConfigurationError(_))));
+}
+
+#[test]
+fn mode_config_shared_by_two_modes_is_not_a_cycle() {
+    let main = format!(
+        "{}{}\n[modes.one]\nconfig = \"sql.toml\"\n\n[modes.two]\nconfig = \"sql.toml\"\n",
+        EMPTY_SECTIONS, METADATA
+    );
+    let sql = format!(
+        "special_rules = []\n\n{}\n[token_categories]\nSql = {{ priority = 1, color = \"blue\" }}\n\n\
+         [patterns]\nSql = [ {{ regex = \"[a-z]+\" }} ]\n\n[context_rules]\n\n[custom_actions]\n",
+        METADATA
+    );
+    let config = load_configs("mode-shared", &[("main.toml", &main), ("sql.toml", &sql)]).unwrap();
+    assert!(!config.get_mode("one").unwrap().is_opaque());
+    assert!(!config.get_mode("two").unwrap().is_opaque());
+}
@@ -0,0 +1,131 @@
+// abap-tokenizer/tests/namespaces.rs
+//! Tests for namespaced identifiers and hyphenated keywords, over the corpus
+//! in `tests/corpus/namespaces.abap`.
+use abap_tokenizer::config::load_toml_config;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use std::fs;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus/namespaces.abap");
+
+fn corpus_tokens() -> Vec<Token> {
+    let source = fs::read_to_string(CORPUS).unwrap();
+    FlexibleTokenizer::new(&source, load_toml_config(CONFIG).unwrap())
+        .tokenize()
+        .unwrap()
+}
+
+/// Returns the type of the token with the given value on a line. Panics if
+/// there is none, so that a wrong line number does not pass silently.
+fn type_of(tokens: &[Token], line: usize, value: &str) -> String {
+    tokens
+        .iter()
+        .find(|token| token.line == line && token.value == value)
+        .unwrap_or_else(|| panic!("no token {:?} on line {}", value, line))
+        .token_type
+        .to_string()
+}
+
+/// Returns the types and values of the tokens on a line, skipping comments.
+fn line_tokens(tokens: &[Token], line: usize) -> Vec<(String, String)> {
+    tokens
+        .iter()
+        .filter(|token| token.line == line && token.token_type.category != "Comment")
+        .map(|token| (token.token_type.to_string(), token.value.clone()))
+        .collect()
+}
+
+fn pair(token_type: &str, value: &str) -> (String, String) {
+    (token_type.to_string(), value.to_string())
+}
+
+#[test]
+fn namespaced_object_names() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 2, "/abc/r_invoice_list"), "Identifier:Namespaced");
+    assert_eq!(type_of(&tokens, 3, "/abc/cl_invoice"), "Identifier:Namespaced");
+    assert_eq!(type_of(&tokens, 4, "/abc/s_line"), "Identifier:Namespaced");
+    assert_eq!(type_of(&tokens, 11, "/abc/t_invoice"), "Identifier:Namespaced");
+}
+
+#[test]
+fn namespaced_class_with_static_component() {
+    let tokens = corpus_tokens();
+    assert_eq!(
+        line_tokens(&tokens, 7)[2..5],
+        [
+            pair("Identifier:Namespaced", "/abc/cl_invoice"),
+            pair("Selector:Class", "=>"),
+            pair("Identifier:MethodName", "create"),
+        ]
+    );
+}
+
+#[test]
+fn division_is_not_a_namespace() {
+    let tokens = corpus_tokens();
+    assert_eq!(
+        line_tokens(&tokens, 8)[2..7],
+        [
+            pair("Identifier:Namespaced", "/abc/cl_invoice"),
+            pair("Selector:Class", "=>"),
+            pair("Identifier", "gv_total"),
+            pair("Operator", "/"),
+            pair("Identifier", "lv_count"),
+        ]
+    );
+    assert_eq!(
+        line_tokens(&tokens, 9)[2..5],
+        [pair("Identifier", "lv_total"), pair("Operator", "/"), pair("Literal:Integer", "2")]
+    );
+}
+
+#[test]
+fn namespaced_structure_component() {
+    let tokens = corpus_tokens();
+    assert_eq!(
+        line_tokens(&tokens, 10)[..3],
+        [
+            pair("Identifier", "ls_line"),
+            pair("Selector:Structure", "-"),
+            pair("Identifier:Namespaced", "/abc/field"),
+        ]
+    );
+}
+
+#[test]
+fn escaped_namespaced_name() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 11, "!/abc/amount"), "Identifier:Escaped");
+}
+
+#[test]
+fn namespace_in_text_literal_is_not_split() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 13, "'/ABC/F_POST'"), "Literal:Text");
+}
+
+#[test]
+fn hyphenated_keywords_are_single_tokens() {
+    let tokens = corpus_tokens();
+    assert_eq!(type_of(&tokens, 4, "FIELD-SYMBOLS"), "Keyword:Declaration");
+    assert_eq!(type_of(&tokens, 5, "CLASS-DATA"), "Keyword:Declaration");
+    assert_eq!(type_of(&tokens, 6, "START-OF-SELECTION"), "Keyword:Event");
+    assert_eq!(type_of(&tokens, 12, "MOVE-CORRESPONDING"), "Keyword:Statement");
+    assert_eq!(type_of(&tokens, 14, "END-OF-SELECTION"), "Keyword:Event");
+    assert_eq!(type_of(&tokens, 15, "TOP-OF-PAGE"), "Keyword:Event");
+    assert!(tokens
+        .iter()
+        .all(|token| token.token_type.to_string() != "Selector:Structure" || token.line == 10));
+}
+
+#[test]
+fn no_unknown_tokens() {
+    let unknown: Vec<_> = corpus_tokens()
+        .into_iter()
+        .filter(|token| token.token_type.category == "Unknown")
+        .map(|token| (token.line, token.value))
+        .collect();
+    assert!(unknown.is_empty(), "{:?}", unknown);
+}
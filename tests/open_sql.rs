@@ -0,0 +1,106 @@
+// abap-tokenizer/tests/open_sql.rs
+//! Tests for Open SQL host variables, column selectors and escaped names,
+//! over the SELECT statements in `tests/corpus/open_sql_select.abap`.
+use abap_tokenizer::config::Dialect;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use std::fs;
+
+const CORPUS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus/open_sql_select.abap");
+
+fn corpus_tokens(dialect: Dialect) -> Vec<Token> {
+    let source = fs::read_to_string(CORPUS).unwrap();
+    FlexibleTokenizer::new(&source, dialect.config().unwrap())
+        .tokenize()
+        .unwrap()
+}
+
+/// Returns the types and values of the tokens on a line, skipping comments.
+fn line_tokens(tokens: &[Token], line: usize) -> Vec<(String, String)> {
+    tokens
+        .iter()
+        .filter(|token| token.line == line && token.token_type.category != "Comment")
+        .map(|token| (token.token_type.to_string(), token.value.clone()))
+        .collect()
+}
+
+/// Returns the type of the `@` marker on a line.
+fn host_marker(tokens: &[Token], line: usize) -> String {
+    line_tokens(tokens, line)
+        .into_iter()
+        .find(|(_, value)| value == "@")
+        .map(|(token_type, _)| token_type)
+        .unwrap_or_else(|| panic!("no @ on line {}", line))
+}
+
+#[test]
+fn host_variables() {
+    for dialect in [Dialect::Abap740, Dialect::Abap750, Dialect::Abap75x] {
+        let tokens = corpus_tokens(dialect);
+        for line in [4, 13, 14, 17] {
+            assert_eq!(host_marker(&tokens, line), "HostVariable:Variable", "{} line {}", dialect, line);
+        }
+    }
+}
+
+#[test]
+fn inline_declarations() {
+    for dialect in [Dialect::Abap740, Dialect::Abap750, Dialect::Abap75x] {
+        let tokens = corpus_tokens(dialect);
+        for line in [5, 10] {
+            assert_eq!(host_marker(&tokens, line), "HostVariable:InlineDeclaration", "{} line {}", dialect, line);
+        }
+    }
+    // FINAL is only known from 7.57
+    let tokens = corpus_tokens(Dialect::Abap75x);
+    assert_eq!(host_marker(&tokens, 19), "HostVariable:InlineDeclaration");
+    assert_eq!(
+        line_tokens(&tokens, 10)[2..5],
+        [
+            ("Keyword:Declaration".to_string(), "DATA".to_string()),
+            ("Punctuation".to_string(), "(".to_string()),
+            ("Identifier".to_string(), "ls_flight".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn host_expressions_from_7_50() {
+    for dialect in [Dialect::Abap750, Dialect::Abap75x] {
+        let tokens = corpus_tokens(dialect);
+        assert_eq!(host_marker(&tokens, 9), "HostVariable:Expression", "{}", dialect);
+    }
+}
+
+#[test]
+fn table_column_selectors() {
+    let tokens = corpus_tokens(Dialect::Abap750);
+    let line = line_tokens(&tokens, 8);
+    let selectors: Vec<_> = line.iter().filter(|(_, value)| value == "~").collect();
+    assert_eq!(selectors.len(), 2);
+    assert!(selectors.iter().all(|(token_type, _)| token_type == "Selector:TableColumn"));
+    assert_eq!(
+        line[6..9],
+        [
+            ("Identifier".to_string(), "s".to_string()),
+            ("Selector:TableColumn".to_string(), "~".to_string()),
+            ("Identifier".to_string(), "carrid".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn escaped_names() {
+    let tokens = corpus_tokens(Dialect::Abap750);
+    assert!(line_tokens(&tokens, 11).contains(&("Identifier:Escaped".to_string(), "!count".to_string())));
+}
+
+#[test]
+fn no_unknown_tokens() {
+    let unknown: Vec<_> = corpus_tokens(Dialect::Abap750)
+        .into_iter()
+        .filter(|token| token.token_type.category == "Unknown")
+        .map(|token| (token.line, token.value))
+        .collect();
+    assert!(unknown.is_empty(), "{:?}", unknown);
+}
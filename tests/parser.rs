@@ -0,0 +1,185 @@
+// abap-tokenizer/tests/parser.rs
+//! Tests for the concrete syntax tree parser: block nesting, unclosed blocks
+//! and recovery at statement boundaries.
+use abap_tokenizer::config::load_toml_config;
+use abap_tokenizer::parser::{parse, SyntaxKind, SyntaxTree};
+use abap_tokenizer::Severity;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+fn parse_source(source: &str) -> SyntaxTree {
+    parse(source, load_toml_config(CONFIG).unwrap()).unwrap()
+}
+
+fn kinds(tree: &SyntaxTree) -> Vec<SyntaxKind> {
+    tree.root.child_nodes().map(|node| node.kind).collect()
+}
+
+#[test]
+fn tree_reproduces_source() {
+    let source = "REPORT zdemo.\n\n\" comment\nDATA lv_count TYPE i. \" trailing\nlv_count = 1.\n";
+    let tree = parse_source(source);
+    assert_eq!(tree.to_string(), source);
+    assert!(tree.diagnostics.is_empty());
+}
+
+#[test]
+fn classifies_statement_families() {
+    let tree = parse_source(
+        "DATA lv_a TYPE i.\n\
+         TYPES ty_a TYPE c.\n\
+         CONSTANTS lc_a TYPE i VALUE 1.\n\
+         FIELD-SYMBOLS <fs> TYPE any.\n\
+         lv_a = 2.\n\
+         lo_obj->run( ).\n\
+         SELECT SINGLE carrid FROM scarr INTO @DATA(lv_carrid).\n",
+    );
+    assert_eq!(
+        kinds(&tree),
+        vec![
+            SyntaxKind::DataDeclaration,
+            SyntaxKind::TypeDeclaration,
+            SyntaxKind::ConstantDeclaration,
+            SyntaxKind::FieldSymbolDeclaration,
+            SyntaxKind::Assignment,
+            SyntaxKind::MethodCall,
+            SyntaxKind::SelectStatement,
+        ]
+    );
+    assert_eq!(tree.root.descendants(SyntaxKind::InlineDeclaration).len(), 1);
+}
+
+#[test]
+fn nests_blocks() {
+    let tree = parse_source(
+        "IF lv_a = 1.\n\
+         \x20 LOOP AT lt_rows INTO ls_row.\n\
+         \x20   CASE ls_row-kind.\n\
+         \x20     WHEN 1.\n\
+         \x20       DO 3 TIMES.\n\
+         \x20       ENDDO.\n\
+         \x20     WHEN OTHERS.\n\
+         \x20   ENDCASE.\n\
+         \x20 ENDLOOP.\n\
+         ELSEIF lv_a = 2.\n\
+         ELSE.\n\
+         ENDIF.\n",
+    );
+    assert!(tree.diagnostics.is_empty());
+    assert_eq!(kinds(&tree), vec![SyntaxKind::IfBlock]);
+
+    let if_block = tree.root.child_nodes().next().unwrap();
+    let loops = if_block.descendants(SyntaxKind::LoopBlock);
+    assert_eq!(loops.len(), 1);
+    let case_block = loops[0].descendants(SyntaxKind::CaseBlock);
+    assert_eq!(case_block.len(), 1);
+    assert_eq!(case_block[0].descendants(SyntaxKind::WhenBranch).len(), 2);
+    assert_eq!(case_block[0].descendants(SyntaxKind::DoBlock).len(), 1);
+    assert_eq!(if_block.descendants(SyntaxKind::ElseIfBranch).len(), 1);
+    assert_eq!(if_block.descendants(SyntaxKind::ElseBranch).len(), 1);
+}
+
+#[test]
+fn nests_class_and_method_implementations() {
+    let tree = parse_source(
+        "CLASS lcl_demo DEFINITION.\n\
+         \x20 PUBLIC SECTION.\n\
+         \x20   METHODS run.\n\
+         ENDCLASS.\n\
+         CLASS lcl_demo IMPLEMENTATION.\n\
+         \x20 METHOD run.\n\
+         \x20   WRITE 'x'.\n\
+         \x20 ENDMETHOD.\n\
+         ENDCLASS.\n",
+    );
+    assert!(tree.diagnostics.is_empty());
+    assert_eq!(
+        kinds(&tree),
+        vec![SyntaxKind::ClassDefinition, SyntaxKind::ClassImplementation]
+    );
+    assert_eq!(tree.root.descendants(SyntaxKind::MethodImplementation).len(), 1);
+}
+
+#[test]
+fn local_friends_does_not_open_a_class_block() {
+    let tree = parse_source(
+        "CLASS lcl_test DEFINITION DEFERRED.\n\
+         CLASS zcl_demo DEFINITION LOCAL FRIENDS lcl_test.\n\
+         CLASS lcl_test DEFINITION FRIENDS zcl_demo.\n\
+         ENDCLASS.\n\
+         DATA lv_a TYPE i.\n",
+    );
+    assert!(tree.diagnostics.is_empty(), "{:?}", tree.diagnostics);
+    assert_eq!(
+        kinds(&tree),
+        vec![
+            SyntaxKind::Statement,
+            SyntaxKind::Statement,
+            SyntaxKind::ClassDefinition,
+            SyntaxKind::DataDeclaration,
+        ]
+    );
+    assert_eq!(tree.root.descendants(SyntaxKind::ClassDefinition).len(), 1);
+}
+
+#[test]
+fn groups_select_clauses() {
+    let tree = parse_source(
+        "SELECT carrid, COUNT(*) AS flights FROM sflight\n\
+         \x20 WHERE fldate > lv_date\n\
+         \x20 GROUP BY carrid\n\
+         \x20 ORDER BY carrid\n\
+         \x20 INTO TABLE lt_counts.\n",
+    );
+    let select = tree.root.child_nodes().next().unwrap();
+    assert_eq!(select.kind, SyntaxKind::SelectStatement);
+    let clauses: Vec<String> = select
+        .child_nodes()
+        .map(|clause| {
+            assert_eq!(clause.kind, SyntaxKind::SqlClause);
+            clause.to_string().split_whitespace().next().unwrap().to_uppercase()
+        })
+        .collect();
+    assert_eq!(clauses, ["SELECT", "FROM", "WHERE", "GROUP", "ORDER", "INTO"]);
+}
+
+#[test]
+fn reports_unclosed_block_at_its_opening_statement() {
+    let tree = parse_source("DATA lv_a TYPE i.\nIF lv_a = 1.\n  LOOP AT lt_rows INTO ls_row.\n  ENDLOOP.\n");
+    assert_eq!(kinds(&tree), vec![SyntaxKind::DataDeclaration, SyntaxKind::IfBlock]);
+    assert_eq!(tree.diagnostics.len(), 1);
+    let diagnostic = &tree.diagnostics[0];
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!((diagnostic.line, diagnostic.column), (2, 1));
+    assert!(diagnostic.message.contains("ENDIF"), "{}", diagnostic.message);
+}
+
+#[test]
+fn reports_unclosed_inner_block_and_closes_outer_one() {
+    let tree = parse_source("IF lv_a = 1.\n  LOOP AT lt_rows INTO ls_row.\nENDIF.\n");
+    assert_eq!(kinds(&tree), vec![SyntaxKind::IfBlock]);
+    assert_eq!(tree.diagnostics.len(), 1);
+    assert_eq!((tree.diagnostics[0].line, tree.diagnostics[0].column), (2, 3));
+    assert!(tree.diagnostics[0].message.contains("ENDLOOP"));
+}
+
+#[test]
+fn recovers_after_unexpected_closing_statement() {
+    let tree = parse_source("ENDIF.\nDATA lv_a TYPE i.\nlv_a = 1.\n");
+    assert_eq!(
+        kinds(&tree),
+        vec![SyntaxKind::Error, SyntaxKind::DataDeclaration, SyntaxKind::Assignment]
+    );
+    assert_eq!(tree.diagnostics.len(), 1);
+    assert_eq!(tree.diagnostics[0].line, 1);
+}
+
+#[test]
+fn recovers_from_unterminated_last_statement() {
+    let source = "DATA lv_a TYPE i.\nlv_a = 1";
+    let tree = parse_source(source);
+    assert_eq!(kinds(&tree), vec![SyntaxKind::DataDeclaration, SyntaxKind::Error]);
+    assert_eq!(tree.diagnostics.len(), 1);
+    assert_eq!((tree.diagnostics[0].line, tree.diagnostics[0].column), (2, 1));
+    assert_eq!(tree.to_string(), source);
+}
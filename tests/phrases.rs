@@ -0,0 +1,146 @@
+// abap-tokenizer/tests/phrases.rs
+//! Tests for keyword phrases: words split over lines or by comments, partial
+//! phrases and the preference for the longest phrase.
+use abap_tokenizer::config::{load_toml_config, TokenizerConfig};
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::token::Token;
+use std::fs;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+/// Configuration with a phrase emitted as members and two phrases starting
+/// with `TYPE`, the shorter one declared first.
+const PHRASES: &str = r#"
+special_rules = [ { token_type = "Comment", start = '"', end = "\n" } ]
+
+[metadata]
+language_version = "test"
+case_sensitive = false
+allow_unicode_identifiers = false
+
+[token_categories]
+Keyword = { priority = 1, color = "blue" }
+Identifier = { priority = 2, color = "black" }
+Punctuation = { priority = 3, color = "gray" }
+
+[patterns]
+Keyword = [ { regex = "\\b(SELECT|FROM|TYPE)\\b" } ]
+Identifier = [ { regex = "[a-zA-Z_]+" } ]
+Punctuation = [ { regex = "[.*]" } ]
+
+[context_rules]
+
+[custom_actions]
+
+[[phrases]]
+words = ["ORDER", "BY"]
+emit = "members"
+
+[[phrases]]
+words = ["TYPE", "TABLE", "OF"]
+subcategory = "Declaration"
+
+[[phrases]]
+words = ["TYPE", "STANDARD", "TABLE", "OF"]
+subcategory = "Declaration"
+"#;
+
+fn tokenize(source: &str, config: TokenizerConfig) -> Vec<Token> {
+    FlexibleTokenizer::new(source, config).tokenize().unwrap()
+}
+
+fn tokenize_default(source: &str) -> Vec<Token> {
+    tokenize(source, load_toml_config(CONFIG).unwrap())
+}
+
+fn phrases_config() -> TokenizerConfig {
+    let path = std::env::temp_dir().join(format!("abap-tokenizer-phrases-{}.toml", std::process::id()));
+    fs::write(&path, PHRASES).unwrap();
+    load_toml_config(path.to_str().unwrap()).unwrap()
+}
+
+fn find<'a>(tokens: &'a [Token], value: &str) -> &'a Token {
+    tokens
+        .iter()
+        .find(|token| token.value == value)
+        .unwrap_or_else(|| panic!("no token {:?}", value))
+}
+
+#[test]
+fn phrase_split_over_a_line_break() {
+    let source = "SELECT * FROM sflight INTO TABLE lt_flights ORDER\n    BY carrid.";
+    let tokens = tokenize_default(source);
+    let order_by = find(&tokens, "ORDER BY");
+    assert_eq!(order_by.token_type.to_string(), "Keyword:Database");
+    assert_eq!((order_by.line, order_by.column), (1, 45));
+    assert_eq!(&source[order_by.span.start..order_by.span.end], "ORDER\n    BY");
+    // The token after the phrase is positioned on the second line
+    let carrid = find(&tokens, "carrid");
+    assert_eq!((carrid.line, carrid.column), (2, 8));
+}
+
+#[test]
+fn comment_in_the_gap_of_a_composite_phrase() {
+    let source = "SELECT * FROM sflight ORDER \"c\n BY carrid.";
+    let tokens = tokenize_default(source);
+    let order_by = find(&tokens, "ORDER BY");
+    assert_eq!(&source[order_by.span.start..order_by.span.end], "ORDER \"c\n BY");
+    // The comment is part of the composite token
+    assert!(tokens.iter().all(|token| token.token_type.category != "Comment"));
+    assert_eq!(find(&tokens, "carrid").line, 2);
+}
+
+#[test]
+fn comment_in_the_gap_of_a_members_phrase() {
+    let tokens = tokenize("SELECT * FROM sflight ORDER \"c\n BY carrid.", phrases_config());
+    let values: Vec<&str> = tokens.iter().skip(4).take(3).map(|token| token.value.as_str()).collect();
+    assert_eq!(values, ["ORDER", "\"c\n", "BY"]);
+    // The comment between the members is tokenized normally
+    assert_eq!(tokens[5].token_type.category, "Comment");
+    assert_eq!(tokens[6].line, 2);
+    assert!(tokens[4].phrase_id.is_some());
+    assert_eq!(tokens[4].phrase_id, tokens[6].phrase_id);
+    assert_eq!(tokens[5].phrase_id, None);
+}
+
+#[test]
+fn partial_phrase_falls_back_to_single_keywords() {
+    let tokens = tokenize_default("DATA lo_ref TYPE REF lv_x.");
+    let types: Vec<(String, &str)> = tokens
+        .iter()
+        .map(|token| (token.token_type.to_string(), token.value.as_str()))
+        .collect();
+    assert_eq!(types[2], ("Keyword:Declaration".to_string(), "TYPE"));
+    // Without `TO`, REF is the name of the type
+    assert_eq!(types[3], ("Identifier:TypeName".to_string(), "REF"));
+    assert!(tokens.iter().all(|token| token.phrase_id.is_none()));
+
+    // A phrase word followed by more word characters is not a match either
+    let tokens = tokenize_default("LOOP ATx.");
+    assert_eq!(tokens[0].value, "LOOP");
+    assert_eq!(tokens[0].phrase_id, None);
+}
+
+#[test]
+fn longest_phrase_wins() {
+    // The shorter phrase is declared first; phrases are tried longest first
+    let tokens = tokenize("DATA lt_a TYPE STANDARD TABLE OF i.", phrases_config());
+    assert_eq!(tokens[2].value, "TYPE STANDARD TABLE OF");
+    assert_eq!(tokens[3].value, "i");
+
+    let tokens = tokenize("DATA lt_a TYPE TABLE OF i.", phrases_config());
+    assert_eq!(tokens[2].value, "TYPE TABLE OF");
+    assert_eq!(tokens[2].token_type.to_string(), "Keyword:Declaration");
+
+    // The phrases of the default profile overlap in the same way
+    for (source, phrase) in [
+        ("DATA lt_a TYPE SORTED TABLE OF mara WITH UNIQUE KEY matnr.", "TYPE SORTED TABLE OF"),
+        ("DATA lt_a TYPE TABLE OF mara.", "TYPE TABLE OF"),
+        ("DATA lo_a TYPE REF TO zcl_a.", "TYPE REF TO"),
+        ("DATA ls_a LIKE LINE OF lt_a.", "LIKE LINE OF"),
+    ] {
+        let tokens = tokenize_default(source);
+        assert_eq!(tokens[2].value, phrase, "{}", source);
+        assert_eq!(tokens[3].token_type.to_string(), "Identifier:TypeName", "{}", source);
+    }
+}
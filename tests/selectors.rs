@@ -0,0 +1,123 @@
+// abap-tokenizer/tests/selectors.rs
+//! Tests for the component selectors `-`, `->`, `=>` and `~`, and for the
+//! grouping of paths with `paths::group_paths`.
+use abap_tokenizer::config::load_toml_config;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::tokenizer::paths::group_paths;
+use abap_tokenizer::tokenizer::token::Token;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+fn tokenize(source: &str) -> Vec<Token> {
+    FlexibleTokenizer::new(source, load_toml_config(CONFIG).unwrap())
+        .tokenize()
+        .unwrap()
+}
+
+/// Returns `type value` pairs, the type as `category` or `category:subcategory`.
+fn types(tokens: &[Token]) -> Vec<String> {
+    tokens
+        .iter()
+        .map(|token| format!("{} {}", token.token_type, token.value))
+        .collect()
+}
+
+#[test]
+fn structure_component_selectors() {
+    assert_eq!(
+        types(&tokenize("ls-a-b")),
+        [
+            "Identifier ls",
+            "Selector:Structure -",
+            "Identifier a",
+            "Selector:Structure -",
+            "Identifier b",
+        ]
+    );
+}
+
+#[test]
+fn object_and_class_selectors() {
+    assert_eq!(
+        types(&tokenize("lo->m( )-f")),
+        [
+            "Identifier lo",
+            "Selector:Object ->",
+            "Identifier:MethodName m",
+            "Punctuation (",
+            "Punctuation )",
+            "Selector:Structure -",
+            "Identifier f",
+        ]
+    );
+    assert_eq!(
+        types(&tokenize("zcl_util=>create")),
+        ["Identifier zcl_util", "Selector:Class =>", "Identifier create"]
+    );
+}
+
+#[test]
+fn detached_minus_stays_an_operator() {
+    assert_eq!(
+        types(&tokenize("x = a - b.")),
+        [
+            "Identifier x",
+            "Operator =",
+            "Identifier a",
+            "Operator -",
+            "Identifier b",
+            "Punctuation .",
+        ]
+    );
+    // A minus touching only one side is not a selector either
+    assert_eq!(types(&tokenize("x = -b."))[2], "Operator -");
+}
+
+#[test]
+fn table_column_selector() {
+    assert_eq!(
+        types(&tokenize("tab~col")),
+        ["Identifier tab", "Selector:TableColumn ~", "Identifier col"]
+    );
+}
+
+#[test]
+fn keywords_after_a_selector_are_component_names() {
+    assert_eq!(types(&tokenize("ls_data-type"))[2], "Identifier type");
+    assert_eq!(types(&tokenize("lo_obj->data"))[2], "Identifier data");
+}
+
+#[test]
+fn group_paths_merges_adjacent_components() {
+    let source = "lv_x = ls-a-b + lo->m( )-f + tab~col - zcl_util=>create.";
+    let grouped = group_paths(tokenize(source));
+    assert_eq!(
+        types(&grouped),
+        [
+            "Identifier lv_x",
+            "Operator =",
+            "Path ls-a-b",
+            "Operator +",
+            "Path lo->m",
+            "Punctuation (",
+            "Punctuation )",
+            "Selector:Structure -",
+            "Identifier f",
+            "Operator +",
+            "Path tab~col",
+            "Operator -",
+            "Path zcl_util=>create",
+            "Punctuation .",
+        ]
+    );
+    // A path keeps the position and span of the whole source text
+    let path = &grouped[2];
+    assert_eq!((path.line, path.column), (1, 8));
+    assert_eq!(&source[path.span.start..path.span.end], "ls-a-b");
+}
+
+#[test]
+fn group_paths_leaves_single_components_alone() {
+    let tokens = tokenize("WRITE lv_x.");
+    assert_eq!(types(&group_paths(tokens.clone())), types(&tokens));
+}
@@ -0,0 +1,122 @@
+// abap-tokenizer/tests/string_templates.rs
+//! Tests for string templates: embedded expressions, nested templates,
+//! escapes and unterminated templates.
+use abap_tokenizer::config::{load_toml_config, TokenizerConfig};
+use abap_tokenizer::parser::parse;
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::{Diagnostic, Severity};
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+fn config() -> TokenizerConfig {
+    load_toml_config(CONFIG).unwrap()
+}
+
+/// Tokenizes a source, returning `category value` pairs and the diagnostics.
+fn tokenize_with(source: &str, config: TokenizerConfig) -> (Vec<String>, Vec<Diagnostic>) {
+    let mut tokenizer = FlexibleTokenizer::new(source, config);
+    let tokens = tokenizer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|token| format!("{} {}", token.token_type.category, token.value))
+        .collect();
+    (tokens, tokenizer.diagnostics().to_vec())
+}
+
+fn tokenize(source: &str) -> Vec<String> {
+    let (tokens, diagnostics) = tokenize_with(source, config());
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    tokens
+}
+
+#[test]
+fn embedded_expressions() {
+    assert_eq!(
+        tokenize("|Hi { lv_name }!|"),
+        [
+            "TemplateStart |",
+            "TemplateLiteral Hi ",
+            "TemplateExprOpen {",
+            "Identifier lv_name",
+            "TemplateExprClose }",
+            "TemplateLiteral !",
+            "TemplateEnd |",
+        ]
+    );
+}
+
+#[test]
+fn nested_templates() {
+    assert_eq!(
+        tokenize("|a{ |b{ c }| }d|"),
+        [
+            "TemplateStart |",
+            "TemplateLiteral a",
+            "TemplateExprOpen {",
+            "TemplateStart |",
+            "TemplateLiteral b",
+            "TemplateExprOpen {",
+            "Identifier c",
+            "TemplateExprClose }",
+            "TemplateEnd |",
+            "TemplateExprClose }",
+            "TemplateLiteral d",
+            "TemplateEnd |",
+        ]
+    );
+}
+
+#[test]
+fn escaped_delimiters() {
+    assert_eq!(
+        tokenize(r"|a\|b\{c\}\\|"),
+        ["TemplateStart |", r"TemplateLiteral a\|b\{c\}\\", "TemplateEnd |"]
+    );
+}
+
+#[test]
+fn templates_inside_method_calls() {
+    let tokens = tokenize("lo_out->write( |Total: { lv_sum }| ).");
+    let start = tokens.iter().position(|token| token == "TemplateStart |").unwrap();
+    assert_eq!(tokens[start - 1], "Punctuation (");
+    assert_eq!(tokens[start + 5], "TemplateEnd |");
+    assert_eq!(tokens[start + 6], "Punctuation )");
+    assert_eq!(tokens.last().unwrap(), "Punctuation .");
+}
+
+#[test]
+fn unterminated_template_is_reported() {
+    let (tokens, diagnostics) = tokenize_with("lv_x = |abc\nlv_y = 1.", config());
+    assert_eq!(tokens[2..4], ["TemplateStart |", "TemplateLiteral abc"]);
+    // Tokenization resumes on the next line
+    assert_eq!(tokens[4], "Identifier lv_y");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 8));
+    assert_eq!(diagnostics[0].message, "Unterminated string template");
+
+    // A template left open inside an embedded expression at the end of input
+    let (_, diagnostics) = tokenize_with("lv_x = |a{ lv_y", config());
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!((diagnostics[0].line, diagnostics[0].column), (1, 8));
+}
+
+#[test]
+fn parser_reports_unterminated_templates() {
+    let tree = parse("lv_x = |abc", config()).unwrap();
+    assert_eq!(tree.diagnostics[0].message, "Unterminated string template");
+}
+
+#[test]
+fn escape_and_multiline_come_from_the_context_rule() {
+    let mut config = config();
+    let rule = config.context_rules.get_mut("TemplateString").unwrap();
+    rule.escape = Some("~".to_string());
+    rule.multiline = Some(true);
+    // `~` escapes the delimiter, `\` is an ordinary character and the
+    // literal part continues on the next line
+    let (tokens, diagnostics) = tokenize_with("|a~|b\\\nc|", config);
+    assert!(diagnostics.is_empty());
+    assert_eq!(tokens, ["TemplateStart |", "TemplateLiteral a~|b\\\nc", "TemplateEnd |"]);
+}
@@ -0,0 +1,86 @@
+// abap-tokenizer/tests/text_symbols.rs
+//! Tests for the inventory of text symbol usages: `TEXT-xxx` references and
+//! literals with a text symbol key.
+use abap_tokenizer::analysis::text_symbols::{find_text_symbols, text_symbols_in_file, TextSymbolKind};
+use abap_tokenizer::config::{load_toml_config, TokenizerConfig};
+use abap_tokenizer::tokenizer::flexible_tokenizer::FlexibleTokenizer;
+use abap_tokenizer::TokenizerError;
+use std::fs;
+
+const CONFIG: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/config/default_config.toml");
+
+fn config() -> TokenizerConfig {
+    load_toml_config(CONFIG).unwrap()
+}
+
+#[test]
+fn text_symbol_reference() {
+    let source = "WRITE TEXT-001.";
+    let tokens = FlexibleTokenizer::new(source, config()).tokenize().unwrap();
+    assert_eq!(tokens[1].token_type.to_string(), "Identifier:TextSymbol");
+
+    let usages = find_text_symbols(&tokens);
+    assert_eq!(usages.len(), 1);
+    assert_eq!(usages[0].key, "001");
+    assert_eq!(usages[0].kind, TextSymbolKind::Reference);
+    assert_eq!(usages[0].text, None);
+    assert_eq!((usages[0].line, usages[0].column), (1, 7));
+    assert_eq!(&source[usages[0].span.start..usages[0].span.end], "TEXT-001");
+}
+
+#[test]
+fn literal_with_text_symbol_key() {
+    let tokens = FlexibleTokenizer::new("MESSAGE 'Hello'(002) TYPE 'I'.", config())
+        .tokenize()
+        .unwrap();
+    assert_eq!(tokens[1].token_type.to_string(), "Literal:TextSymbol");
+    assert_eq!(tokens[1].value, "'Hello'(002)");
+
+    let usages = find_text_symbols(&tokens);
+    assert_eq!(usages.len(), 1);
+    assert_eq!(usages[0].key, "002");
+    assert_eq!(usages[0].kind, TextSymbolKind::Literal);
+    assert_eq!(usages[0].text.as_deref(), Some("Hello"));
+    // The plain literal `'I'` is not a usage
+    assert_eq!(tokens[3].token_type.to_string(), "Literal:Text");
+}
+
+#[test]
+fn keys_are_upper_cased() {
+    let tokens = FlexibleTokenizer::new("WRITE: text-a01, 'Bye'(b02).", config())
+        .tokenize()
+        .unwrap();
+    let keys: Vec<String> = find_text_symbols(&tokens).into_iter().map(|usage| usage.key).collect();
+    assert_eq!(keys, ["A01", "B02"]);
+}
+
+#[test]
+fn names_that_only_look_like_text_symbols_are_not_usages() {
+    // A key must have exactly three characters, and the parentheses must
+    // follow the literal directly
+    let tokens = FlexibleTokenizer::new("WRITE: text-0001, 'Hi' (001), lv_text-001.", config())
+        .tokenize()
+        .unwrap();
+    assert!(find_text_symbols(&tokens).is_empty());
+}
+
+#[test]
+fn usages_of_a_file() {
+    let path = std::env::temp_dir().join(format!("abap-tokenizer-text-symbols-{}.abap", std::process::id()));
+    fs::write(&path, "REPORT ztexts.\nWRITE TEXT-001.\nWRITE / 'Total'(002).\n").unwrap();
+    let path = path.to_str().unwrap();
+    let file = text_symbols_in_file(path, config()).unwrap();
+    assert_eq!(file.file, path);
+    let found: Vec<(usize, usize, &str)> = file
+        .usages
+        .iter()
+        .map(|usage| (usage.line, usage.column, usage.key.as_str()))
+        .collect();
+    assert_eq!(found, [(2, 7, "001"), (3, 9, "002")]);
+}
+
+#[test]
+fn missing_file_is_an_error() {
+    let result = text_symbols_in_file("/nonexistent/ztexts.prog.abap", config());
+    assert!(matches!(result, Err(TokenizerError::IoError(_))));
+}